@@ -0,0 +1,183 @@
+"""Colour maths shared by the palette and colour-accuracy endpoints.
+
+Pure Python on plain lists so it can be tested without OpenCV; main.py does
+the image decoding and clustering and hands the numbers over.
+"""
+import math
+
+
+def _channel(value: float) -> int:
+    return max(0, min(255, round(value)))
+
+
+def swatches_from_clusters(centers_bgr: list, counts: list) -> list[dict]:
+    """Turn k-means cluster centres (OpenCV BGR order) and their pixel counts
+    into swatches with coverage in percent, most common first. Empty clusters
+    are dropped."""
+    total = sum(counts)
+    if total == 0:
+        return []
+    swatches = [
+        {
+            "r": _channel(r),
+            "g": _channel(g),
+            "b": _channel(b),
+            "coverage": 100.0 * count / total,
+        }
+        for (b, g, r), count in zip(centers_bgr, counts)
+        if count > 0
+    ]
+    swatches.sort(key=lambda swatch: swatch["coverage"], reverse=True)
+    return swatches
+
+
+# X-Rite ColorChecker Classic 24, CIELAB under D50, in chart order
+# (row by row from the top left, with the brown dark skin patch first)
+COLORCHECKER_24 = [
+    ("dark_skin", (37.986, 13.555, 14.059)),
+    ("light_skin", (65.711, 18.130, 17.810)),
+    ("blue_sky", (49.927, -4.880, -21.925)),
+    ("foliage", (43.139, -13.095, 21.905)),
+    ("blue_flower", (55.112, 8.844, -25.399)),
+    ("bluish_green", (70.719, -33.397, -0.199)),
+    ("orange", (62.661, 36.067, 57.096)),
+    ("purplish_blue", (40.020, 10.410, -45.964)),
+    ("moderate_red", (51.124, 48.239, 16.248)),
+    ("purple", (30.325, 22.976, -21.587)),
+    ("yellow_green", (72.532, -23.709, 57.255)),
+    ("orange_yellow", (71.941, 19.363, 67.857)),
+    ("blue", (28.778, 14.179, -50.297)),
+    ("green", (55.261, -38.342, 31.370)),
+    ("red", (42.101, 53.378, 28.190)),
+    ("yellow", (81.733, 4.039, 79.819)),
+    ("magenta", (51.935, 49.986, -14.574)),
+    ("cyan", (51.038, -28.631, -28.638)),
+    ("white", (96.539, -0.425, 1.186)),
+    ("neutral_8", (81.257, -0.638, -0.335)),
+    ("neutral_6_5", (66.766, -0.734, -0.504)),
+    ("neutral_5", (50.867, -0.153, -0.270)),
+    ("neutral_3_5", (35.656, -0.421, -1.231)),
+    ("black", (20.461, -0.079, -0.973)),
+]
+CHART_ROWS = 4
+CHART_COLUMNS = 6
+
+# Linear sRGB to XYZ, Bradford-adapted from the sRGB D65 white to D50
+_SRGB_TO_XYZ_D50 = (
+    (0.4360747, 0.3850649, 0.1430804),
+    (0.2225045, 0.7168786, 0.0606169),
+    (0.0139322, 0.0971045, 0.7141733),
+)
+_D50_WHITE = (0.96422, 1.0, 0.82521)
+
+
+def _linearize(value: float) -> float:
+    value /= 255.0
+    if value <= 0.04045:
+        return value / 12.92
+    return ((value + 0.055) / 1.055) ** 2.4
+
+
+def _lab_f(t: float) -> float:
+    if t > (6 / 29) ** 3:
+        return t ** (1 / 3)
+    return t / (3 * (6 / 29) ** 2) + 4 / 29
+
+
+def srgb_to_lab(rgb) -> tuple[float, float, float]:
+    """8-bit sRGB to CIELAB relative to D50, the white the chart values use"""
+    linear = [_linearize(channel) for channel in rgb]
+    x, y, z = (
+        sum(coefficient * value for coefficient, value in zip(row, linear)) / white
+        for row, white in zip(_SRGB_TO_XYZ_D50, _D50_WHITE)
+    )
+    fx, fy, fz = _lab_f(x), _lab_f(y), _lab_f(z)
+    return (116 * fy - 16, 500 * (fx - fy), 200 * (fy - fz))
+
+
+def delta_e_2000(lab1, lab2) -> float:
+    """CIEDE2000 colour difference with unit weighting factors"""
+    l1, a1, b1 = lab1
+    l2, a2, b2 = lab2
+
+    c_bar = (math.hypot(a1, b1) + math.hypot(a2, b2)) / 2
+    g = 0.5 * (1 - math.sqrt(c_bar ** 7 / (c_bar ** 7 + 25 ** 7)))
+    a1p, a2p = (1 + g) * a1, (1 + g) * a2
+    c1p, c2p = math.hypot(a1p, b1), math.hypot(a2p, b2)
+    h1p = math.degrees(math.atan2(b1, a1p)) % 360 if c1p else 0.0
+    h2p = math.degrees(math.atan2(b2, a2p)) % 360 if c2p else 0.0
+
+    delta_l = l2 - l1
+    delta_c = c2p - c1p
+    if c1p * c2p == 0:
+        delta_h = 0.0
+    elif abs(h2p - h1p) <= 180:
+        delta_h = h2p - h1p
+    elif h2p - h1p > 180:
+        delta_h = h2p - h1p - 360
+    else:
+        delta_h = h2p - h1p + 360
+    delta_big_h = 2 * math.sqrt(c1p * c2p) * math.sin(math.radians(delta_h / 2))
+
+    l_bar = (l1 + l2) / 2
+    cp_bar = (c1p + c2p) / 2
+    if c1p * c2p == 0:
+        hp_bar = h1p + h2p
+    elif abs(h1p - h2p) <= 180:
+        hp_bar = (h1p + h2p) / 2
+    elif h1p + h2p < 360:
+        hp_bar = (h1p + h2p + 360) / 2
+    else:
+        hp_bar = (h1p + h2p - 360) / 2
+
+    t = (
+        1
+        - 0.17 * math.cos(math.radians(hp_bar - 30))
+        + 0.24 * math.cos(math.radians(2 * hp_bar))
+        + 0.32 * math.cos(math.radians(3 * hp_bar + 6))
+        - 0.20 * math.cos(math.radians(4 * hp_bar - 63))
+    )
+    delta_theta = 30 * math.exp(-(((hp_bar - 275) / 25) ** 2))
+    r_c = 2 * math.sqrt(cp_bar ** 7 / (cp_bar ** 7 + 25 ** 7))
+    s_l = 1 + 0.015 * (l_bar - 50) ** 2 / math.sqrt(20 + (l_bar - 50) ** 2)
+    s_c = 1 + 0.045 * cp_bar
+    s_h = 1 + 0.015 * cp_bar * t
+    r_t = -math.sin(math.radians(2 * delta_theta)) * r_c
+
+    return math.sqrt(
+        (delta_l / s_l) ** 2
+        + (delta_c / s_c) ** 2
+        + (delta_big_h / s_h) ** 2
+        + r_t * (delta_c / s_c) * (delta_big_h / s_h)
+    )
+
+
+def chart_cells(width: int, height: int) -> list[tuple[int, int, int, int]]:
+    """(x0, y0, x1, y1) of the middle half of each patch, in chart order, for
+    an image cropped to the chart. Sampling the middle keeps the dark borders
+    between patches out of the measurement."""
+    cells = []
+    for row in range(CHART_ROWS):
+        for column in range(CHART_COLUMNS):
+            x0, x1 = width * column // CHART_COLUMNS, width * (column + 1) // CHART_COLUMNS
+            y0, y1 = height * row // CHART_ROWS, height * (row + 1) // CHART_ROWS
+            inset_x, inset_y = (x1 - x0) // 4, (y1 - y0) // 4
+            cells.append((x0 + inset_x, y0 + inset_y, x1 - inset_x, y1 - inset_y))
+    return cells
+
+
+def looks_like_chart(patches_rgb: list) -> bool:
+    """Cheap sanity check that the sampled cells are a ColorChecker: the
+    bottom row must step down from white to black"""
+    if len(patches_rgb) != len(COLORCHECKER_24):
+        return False
+    neutrals = [srgb_to_lab(rgb)[0] for rgb in patches_rgb[-CHART_COLUMNS:]]
+    return all(lighter > darker for lighter, darker in zip(neutrals, neutrals[1:]))
+
+
+def chart_accuracy(patches_rgb: list) -> list[dict]:
+    """Delta E 2000 of each measured patch against the chart reference"""
+    return [
+        {"name": name, "delta_e": delta_e_2000(reference, srgb_to_lab(rgb))}
+        for (name, reference), rgb in zip(COLORCHECKER_24, patches_rgb)
+    ]
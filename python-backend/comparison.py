@@ -0,0 +1,21 @@
+"""Layout geometry for before/after comparison composites."""
+
+LAYOUTS = ["side_by_side", "split_slider", "stacked"]
+
+
+def composite_size(layout: str, width: int, height: int) -> tuple[int, int]:
+    """Size of the composite for images of width x height"""
+    if layout == "side_by_side":
+        return width * 2, height
+    if layout == "stacked":
+        return width, height * 2
+    if layout == "split_slider":
+        return width, height
+    raise ValueError(f"Unknown layout: {layout}")
+
+
+def split_column(position: float, width: int) -> int:
+    """First column taken from the processed image; position is 0-1 of the width"""
+    if not 0.0 <= position <= 1.0:
+        raise ValueError("Split position must be between 0 and 1")
+    return round(position * width)
@@ -0,0 +1,34 @@
+"""Grid geometry for contact sheets: where each cell goes and how each
+thumbnail fits inside it."""
+import math
+
+CELL_PADDING = 8
+LABEL_HEIGHT = 20
+
+
+def grid_layout(count: int, columns: int, thumb_size: int, labels: bool):
+    """Sheet (width, height) and the top-left corner of each cell's thumbnail
+    area, in selection order, filling rows left to right"""
+    columns = max(1, min(columns, count))
+    rows = math.ceil(count / columns)
+    cell_width = thumb_size + CELL_PADDING
+    cell_height = thumb_size + CELL_PADDING + (LABEL_HEIGHT if labels else 0)
+    corners = [
+        (CELL_PADDING + (i % columns) * cell_width, CELL_PADDING + (i // columns) * cell_height)
+        for i in range(count)
+    ]
+    return (CELL_PADDING + columns * cell_width, CELL_PADDING + rows * cell_height), corners
+
+
+def fit_within(width: int, height: int, size: int) -> tuple[int, int]:
+    """Largest size with the same aspect ratio fitting a size x size square"""
+    scale = size / max(width, height)
+    return max(1, round(width * scale)), max(1, round(height * scale))
+
+
+def label_for(name: str, max_chars: int) -> str:
+    """File name shortened in the middle to fit under a thumbnail"""
+    if len(name) <= max_chars:
+        return name
+    keep = max_chars - 1
+    return name[: (keep + 1) // 2] + "~" + name[len(name) - keep // 2:]
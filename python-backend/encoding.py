@@ -0,0 +1,38 @@
+"""Quality search for encoding under a file size limit."""
+from typing import Callable
+
+MIN_QUALITY = 1
+MAX_QUALITY = 100
+
+
+def search_quality(
+    encode: Callable[[int], bytes], max_bytes: int, max_iterations: int
+) -> tuple[int, bytes]:
+    """Binary-search the highest quality whose encoding fits in max_bytes.
+
+    Size grows with quality, so each probe halves the range. When nothing
+    probed fits, the minimum quality encoding is returned as the smallest
+    achievable; the caller compares its size against the target.
+    """
+    encoded: dict[int, bytes] = {}
+
+    def probe(quality: int) -> bytes:
+        if quality not in encoded:
+            encoded[quality] = encode(quality)
+        return encoded[quality]
+
+    low, high = MIN_QUALITY, MAX_QUALITY
+    best = None
+    for _ in range(max(1, max_iterations)):
+        if low > high:
+            break
+        quality = (low + high) // 2
+        if len(probe(quality)) <= max_bytes:
+            best = quality
+            low = quality + 1
+        else:
+            high = quality - 1
+
+    if best is None:
+        best = MIN_QUALITY
+    return best, probe(best)
@@ -0,0 +1,67 @@
+"""Typed job events pushed to /queue/events subscribers alongside status snapshots."""
+import asyncio
+import json
+from datetime import datetime
+from typing import Optional
+
+# Events a slow subscriber may fall behind by before the oldest are dropped
+MAX_PENDING_EVENTS = 256
+
+
+def job_started(job_id: str, path: str, folder_id: Optional[str]) -> dict:
+    return {"type": "job_started", "job_id": job_id, "path": path, "folder_id": folder_id}
+
+
+def job_completed(
+    job_id: str,
+    output_path: str,
+    started_at: Optional[datetime],
+    completed_at: datetime,
+    folder_id: Optional[str],
+) -> dict:
+    processing_time_ms = None
+    if started_at is not None:
+        processing_time_ms = max(0, round((completed_at - started_at).total_seconds() * 1000))
+    return {
+        "type": "job_completed",
+        "job_id": job_id,
+        "output_path": output_path,
+        "processing_time_ms": processing_time_ms,
+        "folder_id": folder_id,
+    }
+
+
+def job_failed(job_id: str, error: Optional[str], folder_id: Optional[str]) -> dict:
+    return {
+        "type": "job_failed",
+        "job_id": job_id,
+        "error": error or "Unknown error",
+        "folder_id": folder_id,
+    }
+
+
+def sse(payload: dict) -> str:
+    """One Server-Sent Events message"""
+    return f"data: {json.dumps(payload)}\n\n"
+
+
+class EventBroadcaster:
+    """Fans events out to every connected /queue/events stream"""
+
+    def __init__(self):
+        self.subscribers: set[asyncio.Queue] = set()
+
+    def subscribe(self) -> asyncio.Queue:
+        events: asyncio.Queue = asyncio.Queue(maxsize=MAX_PENDING_EVENTS)
+        self.subscribers.add(events)
+        return events
+
+    def unsubscribe(self, events: asyncio.Queue):
+        self.subscribers.discard(events)
+
+    def publish(self, event: dict):
+        for events in self.subscribers:
+            # Never block the queue on a stalled client; drop its oldest event
+            if events.full():
+                events.get_nowait()
+            events.put_nowait(event)
@@ -7,8 +7,20 @@ import cv2
 import numpy as np
 from pathlib import Path
 import os
+import shutil
 import asyncio
-import json
+import base64
+
+import colors
+import comparison
+import contact_sheet
+import encoding
+import job_events
+import metadata_io
+import profiles
+import subjects
+import tiling
+from processors import models
 
 from queue_manager import (
     processing_queue, 
@@ -46,6 +58,11 @@ class ImageInfoResponse(BaseModel):
 class ProcessRequest(BaseModel):
     path: str
     operations: list[dict]
+    # Lets the client cancel the job once it stops waiting for it
+    job_id: Optional[str] = None
+    profile: str = profiles.DEFAULT_PROFILE
+    # Originating hot folder, echoed back so results can be attributed
+    folder_id: Optional[str] = None
 
 
 class HealthResponse(BaseModel):
@@ -112,18 +129,630 @@ async def get_image_info(request: ImageInfoRequest):
         raise HTTPException(status_code=500, detail=f"Error reading image: {str(e)}")
 
 
-@app.post("/process")
-async def process_image(request: ProcessRequest):
-    """Process an image with specified operations"""
+class ThumbnailRequest(BaseModel):
+    path: str
+    width: int
+    height: int
+    grayscale: bool = False
+
+
+class ThumbnailResponse(BaseModel):
+    width: int
+    height: int
+    channels: int
+    # Row-major 8-bit samples, interleaved BGR unless grayscale
+    pixels: list[int]
+
+
+# Raw pixels go back as JSON, so keep thumbnails small
+MAX_THUMBNAIL_SIDE = 256
+
+
+@app.post("/image/thumbnail", response_model=ThumbnailResponse)
+async def get_thumbnail(request: ThumbnailRequest):
+    """Downscale an image and return its raw pixels"""
+    if not (0 < request.width <= MAX_THUMBNAIL_SIDE and 0 < request.height <= MAX_THUMBNAIL_SIDE):
+        raise HTTPException(
+            status_code=400,
+            detail=f"Thumbnail size must be between 1 and {MAX_THUMBNAIL_SIDE} pixels",
+        )
+
+    path = Path(request.path)
+    if not path.is_file():
+        raise HTTPException(status_code=404, detail="Image file not found")
+
+    flags = cv2.IMREAD_GRAYSCALE if request.grayscale else cv2.IMREAD_COLOR
+    image = cv2.imread(str(path), flags)
+    if image is None:
+        raise HTTPException(status_code=400, detail="Invalid image file")
+
+    thumbnail = cv2.resize(image, (request.width, request.height), interpolation=cv2.INTER_AREA)
+    return ThumbnailResponse(
+        width=request.width,
+        height=request.height,
+        channels=1 if thumbnail.ndim == 2 else thumbnail.shape[2],
+        pixels=thumbnail.flatten().tolist(),
+    )
+
+
+class PaletteRequest(BaseModel):
+    path: str
+    count: int
+
+
+class ColorSwatch(BaseModel):
+    r: int
+    g: int
+    b: int
+    coverage: float  # percent of the image
+
+
+MAX_PALETTE_COLORS = 16
+# Clustering runs on a downscaled copy; a palette doesn't need every pixel
+PALETTE_SAMPLE_SIDE = 256
+
+
+@app.post("/image/palette", response_model=list[ColorSwatch])
+async def extract_palette(request: PaletteRequest):
+    """Find the dominant colors of an image with k-means"""
+    if not 1 <= request.count <= MAX_PALETTE_COLORS:
+        raise HTTPException(
+            status_code=400,
+            detail=f"Color count must be between 1 and {MAX_PALETTE_COLORS}",
+        )
+
+    path = Path(request.path)
+    if not path.is_file():
+        raise HTTPException(status_code=404, detail="Image file not found")
+    image = cv2.imread(str(path), cv2.IMREAD_COLOR)
+    if image is None:
+        raise HTTPException(status_code=400, detail="Invalid image file")
+
+    height, width = image.shape[:2]
+    scale = min(1.0, PALETTE_SAMPLE_SIDE / max(height, width))
+    if scale < 1.0:
+        size = (max(1, round(width * scale)), max(1, round(height * scale)))
+        image = cv2.resize(image, size, interpolation=cv2.INTER_AREA)
+    pixels = image.reshape(-1, 3).astype(np.float32)
+
+    count = min(request.count, len(pixels))
+    criteria = (cv2.TERM_CRITERIA_EPS + cv2.TERM_CRITERIA_MAX_ITER, 20, 1.0)
+    _, labels, centers = cv2.kmeans(pixels, count, None, criteria, 3, cv2.KMEANS_PP_CENTERS)
+    counts = np.bincount(labels.flatten(), minlength=count)
+    return colors.swatches_from_clusters(centers.tolist(), counts.tolist())
+
+
+class ColorAccuracyRequest(BaseModel):
+    path: str
+    chart: str
+
+
+class PatchAccuracy(BaseModel):
+    name: str
+    delta_e: float
+
+
+class ColorAccuracyResponse(BaseModel):
+    detected: bool
+    patches: list[PatchAccuracy] = []
+
+
+SUPPORTED_CHARTS = ["color_checker24"]
+# Spread (8-bit) above which a sampled cell is not a single flat patch
+MAX_PATCH_STDDEV = 20.0
+
+
+@app.post("/image/color-accuracy", response_model=ColorAccuracyResponse)
+async def measure_color_accuracy(request: ColorAccuracyRequest):
+    """Measure each patch of a reference chart against its known color.
+
+    The image must be cropped to the chart, straight on, with the dark skin
+    patch at the top left. Anything else is reported as not detected.
+    """
+    if request.chart not in SUPPORTED_CHARTS:
+        raise HTTPException(status_code=400, detail=f"Unsupported chart: {request.chart}")
+
+    path = Path(request.path)
+    if not path.is_file():
+        raise HTTPException(status_code=404, detail="Image file not found")
+    image = cv2.imread(str(path), cv2.IMREAD_COLOR)
+    if image is None:
+        raise HTTPException(status_code=400, detail="Invalid image file")
+
+    height, width = image.shape[:2]
+    patches = []
+    for x0, y0, x1, y1 in colors.chart_cells(width, height):
+        cell = image[y0:y1, x0:x1].reshape(-1, 3)
+        if cell.size == 0 or cell.std(axis=0).max() > MAX_PATCH_STDDEV:
+            return ColorAccuracyResponse(detected=False)
+        b, g, r = np.median(cell, axis=0)
+        patches.append((r, g, b))
+
+    if not colors.looks_like_chart(patches):
+        return ColorAccuracyResponse(detected=False)
+    return ColorAccuracyResponse(detected=True, patches=colors.chart_accuracy(patches))
+
+
+class DetectedSubject(BaseModel):
+    x: int
+    y: int
+    width: int
+    height: int
+    confidence: float
+
+
+class SubjectsResponse(BaseModel):
+    subjects: list[DetectedSubject]
+
+
+# Faces are the subjects that matter most for crops of people; the cascade
+# ships with opencv-python, so no model download is needed
+_face_cascade = None
+
+
+def face_cascade():
+    global _face_cascade
+    if _face_cascade is None:
+        _face_cascade = cv2.CascadeClassifier(
+            cv2.data.haarcascades + "haarcascade_frontalface_default.xml"
+        )
+    return _face_cascade
+
+
+@app.post("/image/subjects", response_model=SubjectsResponse)
+async def detect_subjects(request: ImageInfoRequest):
+    """Find faces to keep in frame when suggesting crops"""
+    path = Path(request.path)
+    if not path.is_file():
+        raise HTTPException(status_code=404, detail="Image file not found")
+    image = cv2.imread(str(path), cv2.IMREAD_GRAYSCALE)
+    if image is None:
+        raise HTTPException(status_code=400, detail="Invalid image file")
+
+    height, width = image.shape
+    boxes, _, weights = face_cascade().detectMultiScale3(
+        cv2.equalizeHist(image),
+        scaleFactor=1.1,
+        minNeighbors=5,
+        minSize=(max(24, min(width, height) // 40),) * 2,
+        outputRejectLevels=True,
+    )
+    found = subjects.subjects_from_detections(
+        [tuple(box) for box in boxes], [float(w) for w in np.ravel(weights)], width, height
+    )
+    return SubjectsResponse(subjects=found)
+
+
+class CompareLayout(BaseModel):
+    type: str
+    position: Optional[float] = None
+
+
+class CompareRequest(BaseModel):
+    original_path: str
+    processed_path: str
+    layout: CompareLayout
+    # Expected composite size
+    width: int
+    height: int
+
+
+class CompareResponse(BaseModel):
+    width: int
+    height: int
+    format: str
+    data: str  # base64
+
+
+@app.post("/image/compare", response_model=CompareResponse)
+async def compare_images(request: CompareRequest):
+    """Composite an original and its processed version into one preview"""
+    original = cv2.imread(request.original_path, cv2.IMREAD_COLOR)
+    processed = cv2.imread(request.processed_path, cv2.IMREAD_COLOR)
+    if original is None or processed is None:
+        raise HTTPException(status_code=400, detail="Invalid image file")
+
+    height, width = original.shape[:2]
+    if processed.shape[:2] != (height, width):
+        processed = cv2.resize(processed, (width, height), interpolation=cv2.INTER_AREA)
+
+    layout = request.layout.type
+    try:
+        if comparison.composite_size(layout, width, height) != (request.width, request.height):
+            raise HTTPException(status_code=400, detail="Composite size does not match the layout")
+        if layout == "side_by_side":
+            composite = np.hstack([original, processed])
+        elif layout == "stacked":
+            composite = np.vstack([original, processed])
+        else:
+            position = request.layout.position
+            split = comparison.split_column(0.5 if position is None else position, width)
+            composite = original.copy()
+            composite[:, split:] = processed[:, split:]
+            # Thin divider so the split reads at a glance
+            cv2.line(composite, (split, 0), (split, height - 1), (255, 255, 255), 2)
+    except ValueError as e:
+        raise HTTPException(status_code=400, detail=str(e))
+
+    encoded, buffer = cv2.imencode(".jpg", composite, [cv2.IMWRITE_JPEG_QUALITY, 90])
+    if not encoded:
+        raise HTTPException(status_code=500, detail="Error encoding comparison preview")
+    return CompareResponse(
+        width=composite.shape[1],
+        height=composite.shape[0],
+        format="jpeg",
+        data=base64.b64encode(buffer.tobytes()).decode("ascii"),
+    )
+
+
+class ContactSheetRequest(BaseModel):
+    paths: list[str]
+    columns: int
+    thumb_size: int
+    labels: bool = True
+    output_path: str
+
+
+class ContactSheetResponse(BaseModel):
+    output_path: str
+    # Inputs that could not be decoded; their cells are left as placeholders
+    failed_paths: list[str]
+
+
+SHEET_BACKGROUND = (240, 240, 240)
+PLACEHOLDER_COLOR = (200, 200, 200)
+# FONT_HERSHEY_SIMPLEX at LABEL_SCALE is about this many pixels per character
+LABEL_SCALE = 0.4
+LABEL_CHAR_WIDTH = 7
+
+
+@app.post("/image/contact-sheet", response_model=ContactSheetResponse)
+async def generate_contact_sheet(request: ContactSheetRequest):
+    """Composite thumbnails of the selection into one labelled grid image"""
+    if not request.paths or request.columns < 1 or request.thumb_size < 1:
+        raise HTTPException(
+            status_code=400, detail="Contact sheet needs images, columns and a size"
+        )
+
+    size = request.thumb_size
+    (sheet_width, sheet_height), corners = contact_sheet.grid_layout(
+        len(request.paths), request.columns, size, request.labels
+    )
+    sheet = np.full((sheet_height, sheet_width, 3), SHEET_BACKGROUND, dtype=np.uint8)
+
+    failed = []
+    for path, (x, y) in zip(request.paths, corners):
+        image = cv2.imread(path, cv2.IMREAD_COLOR)
+        if image is None:
+            failed.append(path)
+            cv2.rectangle(sheet, (x, y), (x + size - 1, y + size - 1), PLACEHOLDER_COLOR, -1)
+            cv2.line(sheet, (x, y), (x + size - 1, y + size - 1), SHEET_BACKGROUND, 2)
+            cv2.line(sheet, (x + size - 1, y), (x, y + size - 1), SHEET_BACKGROUND, 2)
+        else:
+            height, width = image.shape[:2]
+            thumb_width, thumb_height = contact_sheet.fit_within(width, height, size)
+            thumb = cv2.resize(image, (thumb_width, thumb_height), interpolation=cv2.INTER_AREA)
+            left = x + (size - thumb_width) // 2
+            top = y + (size - thumb_height) // 2
+            sheet[top:top + thumb_height, left:left + thumb_width] = thumb
+
+        if request.labels:
+            label = contact_sheet.label_for(Path(path).name, max(1, size // LABEL_CHAR_WIDTH))
+            baseline = y + size + contact_sheet.LABEL_HEIGHT - 6
+            cv2.putText(
+                sheet, label, (x, baseline), cv2.FONT_HERSHEY_SIMPLEX, LABEL_SCALE, (40, 40, 40), 1,
+                cv2.LINE_AA,
+            )
+
+    if not cv2.imwrite(request.output_path, sheet):
+        raise HTTPException(status_code=500, detail="Error writing contact sheet")
+    return ContactSheetResponse(output_path=request.output_path, failed_paths=failed)
+
+
+class TargetSizeRequest(BaseModel):
+    path: str
+    output_path: str
+    format: str
+    max_bytes: int
+    max_iterations: int = 8
+
+
+class TargetSizeResponse(BaseModel):
+    output_path: str
+    quality: int
+    size_bytes: int
+
+
+QUALITY_FLAGS = {
+    "jpeg": (".jpg", cv2.IMWRITE_JPEG_QUALITY),
+    "jpg": (".jpg", cv2.IMWRITE_JPEG_QUALITY),
+    "webp": (".webp", cv2.IMWRITE_WEBP_QUALITY),
+}
+
+
+@app.post("/encode/target-size")
+async def encode_target_size(request: TargetSizeRequest):
+    if not os.path.exists(request.path):
+        raise HTTPException(status_code=404, detail="File not found")
+    if request.max_bytes <= 0:
+        raise HTTPException(status_code=400, detail="Target size must be greater than zero")
+    flags = QUALITY_FLAGS.get(request.format.lower())
+    if flags is None:
+        raise HTTPException(
+            status_code=400, detail=f"Target-size encoding is not supported for '{request.format}'"
+        )
+    extension, quality_flag = flags
+
+    image = cv2.imread(request.path)
+    if image is None:
+        raise HTTPException(status_code=400, detail="Could not load image")
+
+    def encode(quality: int) -> bytes:
+        ok, buffer = cv2.imencode(extension, image, [quality_flag, quality])
+        if not ok:
+            raise HTTPException(status_code=500, detail="Error encoding image")
+        return buffer.tobytes()
+
+    quality, data = encoding.search_quality(encode, request.max_bytes, request.max_iterations)
+
+    # Written even when over the target; the caller reports the shortfall
+    with open(request.output_path, "wb") as output:
+        output.write(data)
+    return TargetSizeResponse(
+        output_path=request.output_path, quality=quality, size_bytes=len(data)
+    )
+
+
+def missing_weights(error: FileNotFoundError) -> HTTPException:
+    return HTTPException(
+        status_code=503, detail=f"Model weights are not installed: {Path(error.filename).name}"
+    )
+
+
+@app.get("/processors/{processor_type}/model")
+async def get_processor_model(processor_type: str):
+    """Model provenance for ML-backed processors; null for everything else"""
+    try:
+        return {"model": models.model_info(processor_type)}
+    except FileNotFoundError as error:
+        raise missing_weights(error)
+
+
+# Extensions OpenCV can decode and encode in this build
+INPUT_FORMATS = ["jpg", "jpeg", "png", "webp", "bmp", "tiff", "tif"]
+OUTPUT_FORMATS = ["jpg", "jpeg", "png", "webp", "tiff"]
+
+
+class SupportedFormatsResponse(BaseModel):
+    input: list[str]
+    output: list[str]
+
+
+@app.get("/formats", response_model=SupportedFormatsResponse)
+async def get_supported_formats():
+    """List the file extensions the engine can read and write"""
+    return SupportedFormatsResponse(input=INPUT_FORMATS, output=OUTPUT_FORMATS)
+
+
+# Jobs currently running under /process, by client-supplied job_id
+active_jobs: dict[str, asyncio.Task] = {}
+# Jobs stopped through /process/{job_id}/cancel, as opposed to a client
+# disconnect or server shutdown cancelling the request
+cancel_requested: set[str] = set()
+
+
+async def run_operations(request: ProcessRequest):
+    try:
+        operations = profiles.apply_profile(request.operations, request.profile)
+    except ValueError as error:
+        raise HTTPException(status_code=400, detail=str(error))
+    try:
+        node_models = models.node_models(operations)
+    except FileNotFoundError as error:
+        raise missing_weights(error)
     # TODO: Implement actual processing pipeline
     # For now, just return success
     return {
         "status": "completed",
         "output_path": request.path,
-        "operations_applied": len(request.operations)
+        "operations_applied": len(operations),
+        "profile": request.profile,
+        "folder_id": request.folder_id,
+        "models": node_models,
     }
 
 
+@app.post("/process")
+async def process_image(request: ProcessRequest):
+    """Process an image with specified operations"""
+    task = asyncio.ensure_future(run_operations(request))
+    if request.job_id:
+        active_jobs[request.job_id] = task
+    try:
+        return await task
+    except asyncio.CancelledError:
+        if request.job_id in cancel_requested and task.cancelled():
+            raise HTTPException(status_code=409, detail="Job cancelled")
+        raise
+    finally:
+        if request.job_id:
+            active_jobs.pop(request.job_id, None)
+            cancel_requested.discard(request.job_id)
+
+
+@app.post("/process/{job_id}/cancel")
+async def cancel_processing(job_id: str):
+    """Cancel a running /process job"""
+    task = active_jobs.get(job_id)
+    if task is None:
+        raise HTTPException(status_code=404, detail="Job not found")
+    cancel_requested.add(job_id)
+    task.cancel()
+    return {"status": "cancelled"}
+
+
+def apply_operations(image: np.ndarray, operations: list[dict]) -> np.ndarray:
+    # TODO: Implement actual processing pipeline, shared with /process
+    return image
+
+
+class TileRegion(BaseModel):
+    index: int
+    x: int
+    y: int
+    width: int
+    height: int
+
+
+class TileRequest(BaseModel):
+    job_id: str
+    path: str
+    output_path: str
+    operations: list[dict]
+    tile: TileRegion
+    # Pixels of context the tile takes from each neighbour
+    overlap: int
+
+
+class AssembledTile(TileRegion):
+    path: str
+
+
+class AssembleRequest(BaseModel):
+    job_id: str
+    tiles: list[AssembledTile]
+    overlap: int
+    width: int
+    height: int
+    output_path: str
+
+
+@app.post("/process/tile")
+async def process_tile(request: TileRequest):
+    """Process one tile of a large image and write it out on its own"""
+    image = cv2.imread(request.path, cv2.IMREAD_UNCHANGED)
+    if image is None:
+        raise HTTPException(status_code=400, detail="Invalid image file")
+
+    tile = request.tile
+    height, width = image.shape[:2]
+    try:
+        tiling.check_tile(tile.x, tile.y, tile.width, tile.height, width, height)
+    except ValueError as e:
+        raise HTTPException(status_code=400, detail=str(e))
+
+    region = image[tile.y:tile.y + tile.height, tile.x:tile.x + tile.width]
+    processed = apply_operations(region, request.operations)
+    if processed.shape[:2] != region.shape[:2]:
+        raise HTTPException(status_code=500, detail="Operations changed the tile size")
+    if not cv2.imwrite(request.output_path, processed):
+        raise HTTPException(status_code=500, detail=f"Error writing tile {tile.index}")
+    return {"status": "completed", "output_path": request.output_path}
+
+
+@app.post("/process/assemble")
+async def assemble_tiles(request: AssembleRequest):
+    """Blend processed tiles back into one image, feathering across the overlaps"""
+    if not request.tiles:
+        raise HTTPException(status_code=400, detail="No tiles to assemble")
+
+    canvas = None
+    weights = np.zeros((request.height, request.width), dtype=np.float32)
+    for tile in request.tiles:
+        try:
+            tiling.check_tile(
+                tile.x, tile.y, tile.width, tile.height, request.width, request.height
+            )
+        except ValueError as e:
+            raise HTTPException(status_code=400, detail=str(e))
+        pixels = cv2.imread(tile.path, cv2.IMREAD_UNCHANGED)
+        if pixels is None:
+            raise HTTPException(status_code=400, detail=f"Tile {tile.index} could not be read")
+        if pixels.shape[:2] != (tile.height, tile.width):
+            raise HTTPException(status_code=400, detail=f"Tile {tile.index} has the wrong size")
+
+        if canvas is None:
+            dtype = pixels.dtype
+            canvas = np.zeros((request.height, request.width) + pixels.shape[2:], dtype=np.float32)
+        weight = np.outer(
+            tiling.feather_weights(tile.y, tile.height, request.height, request.overlap),
+            tiling.feather_weights(tile.x, tile.width, request.width, request.overlap),
+        ).astype(np.float32)
+        rows = slice(tile.y, tile.y + tile.height)
+        columns = slice(tile.x, tile.x + tile.width)
+        canvas[rows, columns] += pixels * (weight if pixels.ndim == 2 else weight[..., None])
+        weights[rows, columns] += weight
+
+    if (weights == 0).any():
+        raise HTTPException(status_code=400, detail="Tiles do not cover the whole image")
+    blended = canvas / (weights if canvas.ndim == 2 else weights[..., None])
+    if np.issubdtype(dtype, np.integer):
+        limits = np.iinfo(dtype)
+        blended = np.clip(np.rint(blended), limits.min, limits.max)
+    if not cv2.imwrite(request.output_path, blended.astype(dtype)):
+        raise HTTPException(status_code=500, detail="Error writing assembled image")
+    return {"status": "completed", "output_path": request.output_path}
+
+
+# Volume outputs are written to; the desktop app can't see it when the engine is remote
+OUTPUT_DIR = os.environ.get("NSTANT_OUTPUT_DIR", ".")
+
+
+class DiskSpaceResponse(BaseModel):
+    free_bytes: int
+    total_bytes: int
+
+
+@app.get("/system/disk-space", response_model=DiskSpaceResponse)
+async def get_disk_space():
+    """Report free and total bytes on the output volume"""
+    try:
+        usage = shutil.disk_usage(OUTPUT_DIR)
+    except OSError as e:
+        raise HTTPException(status_code=500, detail=f"Error reading disk space: {str(e)}")
+    return DiskSpaceResponse(free_bytes=usage.free, total_bytes=usage.total)
+
+
+class ImageMetadata(BaseModel):
+    creator: Optional[str] = None
+    copyright: Optional[str] = None
+    keywords: list[str] = []
+    description: Optional[str] = None
+
+
+class EmbedMetadataRequest(BaseModel):
+    path: str
+    metadata: ImageMetadata
+    iptc: bool = False
+
+
+@app.post("/image/metadata")
+async def embed_metadata(request: EmbedMetadataRequest):
+    """Write XMP (and IPTC where supported) metadata into an image"""
+    path = Path(request.path)
+    if not path.is_file():
+        raise HTTPException(status_code=404, detail="Image file not found")
+    try:
+        metadata_io.write_metadata(path, request.metadata.dict(), request.iptc)
+    except ValueError as e:
+        raise HTTPException(status_code=400, detail=str(e))
+    except OSError as e:
+        raise HTTPException(status_code=500, detail=f"Error writing metadata: {str(e)}")
+    return {"status": "embedded"}
+
+
+@app.post("/image/metadata/read", response_model=ImageMetadata)
+async def read_metadata(request: ImageInfoRequest):
+    """Read back the XMP metadata embedded in an image"""
+    path = Path(request.path)
+    if not path.is_file():
+        raise HTTPException(status_code=404, detail="Image file not found")
+    try:
+        return ImageMetadata(**metadata_io.read_metadata(path))
+    except ValueError as e:
+        raise HTTPException(status_code=400, detail=str(e))
+
+
 # Queue Management Endpoints
 @app.post("/queue/add", response_model=QueueItem)
 async def add_to_queue(request: AddToQueueRequest):
@@ -157,10 +786,11 @@ async def get_next_queue_item():
 async def update_queue_item_status(
     item_id: str, 
     status: QueueItemStatus,
-    error: Optional[str] = None
+    error: Optional[str] = None,
+    output_path: Optional[str] = None
 ):
     """Update the status of a queue item"""
-    item = await processing_queue.update_item(item_id, status, error)
+    item = await processing_queue.update_item(item_id, status, error, output_path)
     if not item:
         raise HTTPException(status_code=404, detail="Queue item not found")
     return item
@@ -199,12 +829,25 @@ async def clear_queue():
 # Server-Sent Events for real-time updates
 @app.get("/queue/events")
 async def queue_events():
-    """Stream queue status updates via Server-Sent Events"""
+    """Stream queue status snapshots and typed job events via Server-Sent Events"""
     async def event_generator():
-        while True:
-            status = await processing_queue.get_status()
-            yield f"data: {json.dumps(status.dict())}\n\n"
-            await asyncio.sleep(1)  # Update every second
+        events = processing_queue.events.subscribe()
+        loop = asyncio.get_running_loop()
+        try:
+            while True:
+                status = await processing_queue.get_status()
+                yield job_events.sse(status.dict())
+
+                # Job events go out as they happen between the per-second snapshots
+                deadline = loop.time() + 1
+                while (remaining := deadline - loop.time()) > 0:
+                    try:
+                        event = await asyncio.wait_for(events.get(), remaining)
+                    except asyncio.TimeoutError:
+                        break
+                    yield job_events.sse(event)
+        finally:
+            processing_queue.events.unsubscribe(events)
     
     return StreamingResponse(
         event_generator(),
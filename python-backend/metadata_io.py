@@ -0,0 +1,322 @@
+"""Embed and read back delivery metadata (creator, copyright, keywords, description).
+
+XMP is written for every supported format. IPTC-IIM is written only where the
+container has a standard place for it (JPEG APP13, TIFF tag 33723). JPEG, PNG
+and WebP are edited at the container level, so the image data is never
+re-encoded; TIFF goes through Pillow.
+"""
+import struct
+import zlib
+from pathlib import Path
+from typing import Optional
+from xml.etree import ElementTree
+from xml.sax.saxutils import escape
+
+XMP_JPEG_HEADER = b"http://ns.adobe.com/xap/1.0/\x00"
+PHOTOSHOP_JPEG_HEADER = b"Photoshop 3.0\x00"
+PNG_SIGNATURE = b"\x89PNG\r\n\x1a\n"
+PNG_XMP_KEYWORD = b"XML:com.adobe.xmp"
+TIFF_XMP_TAG = 700
+TIFF_IPTC_TAG = 33723
+
+RDF_NS = "http://www.w3.org/1999/02/22-rdf-syntax-ns#"
+DC_NS = "http://purl.org/dc/elements/1.1/"
+
+# IIM record 2 dataset numbers
+IPTC_KEYWORDS = 25
+IPTC_BYLINE = 80
+IPTC_COPYRIGHT = 116
+IPTC_CAPTION = 120
+
+
+def _alt(value: str) -> str:
+    return f'<rdf:Alt><rdf:li xml:lang="x-default">{escape(value)}</rdf:li></rdf:Alt>'
+
+
+def build_xmp(metadata: dict) -> bytes:
+    fields = []
+    if metadata.get("creator"):
+        creator = escape(metadata["creator"])
+        fields.append(f"<dc:creator><rdf:Seq><rdf:li>{creator}</rdf:li></rdf:Seq></dc:creator>")
+    if metadata.get("copyright"):
+        fields.append(f"<dc:rights>{_alt(metadata['copyright'])}</dc:rights>")
+    if metadata.get("keywords"):
+        items = "".join(f"<rdf:li>{escape(k)}</rdf:li>" for k in metadata["keywords"])
+        fields.append(f"<dc:subject><rdf:Bag>{items}</rdf:Bag></dc:subject>")
+    if metadata.get("description"):
+        fields.append(f"<dc:description>{_alt(metadata['description'])}</dc:description>")
+
+    packet = (
+        '<?xpacket begin="\ufeff" id="W5M0MpCehiHzreSzNTczkc9d"?>'
+        '<x:xmpmeta xmlns:x="adobe:ns:meta/">'
+        f'<rdf:RDF xmlns:rdf="{RDF_NS}">'
+        f'<rdf:Description rdf:about="" xmlns:dc="{DC_NS}">'
+        + "".join(fields)
+        + "</rdf:Description></rdf:RDF></x:xmpmeta>"
+        '<?xpacket end="w"?>'
+    )
+    return packet.encode("utf-8")
+
+
+def parse_xmp(packet: bytes) -> dict:
+    start = packet.find(b"<x:xmpmeta")
+    end = packet.rfind(b"</x:xmpmeta>")
+    if start < 0 or end < 0:
+        raise ValueError("Malformed XMP packet")
+    root = ElementTree.fromstring(packet[start:end + len(b"</x:xmpmeta>")])
+
+    def values(name: str) -> list:
+        element = root.find(f".//{{{DC_NS}}}{name}")
+        if element is None:
+            return []
+        return [li.text or "" for li in element.iter(f"{{{RDF_NS}}}li")]
+
+    def first(name: str) -> Optional[str]:
+        found = values(name)
+        return found[0] if found else None
+
+    return {
+        "creator": first("creator"),
+        "copyright": first("rights"),
+        "keywords": values("subject"),
+        "description": first("description"),
+    }
+
+
+def build_iptc(metadata: dict) -> bytes:
+    # Record 1:90 declares the text as UTF-8
+    datasets = [struct.pack(">BBBH", 0x1C, 1, 90, 3) + b"\x1b%G"]
+
+    def add(number: int, value: str):
+        data = value.encode("utf-8")
+        datasets.append(struct.pack(">BBBH", 0x1C, 2, number, len(data)) + data)
+
+    if metadata.get("creator"):
+        add(IPTC_BYLINE, metadata["creator"])
+    if metadata.get("copyright"):
+        add(IPTC_COPYRIGHT, metadata["copyright"])
+    for keyword in metadata.get("keywords") or []:
+        add(IPTC_KEYWORDS, keyword)
+    if metadata.get("description"):
+        add(IPTC_CAPTION, metadata["description"])
+    return b"".join(datasets)
+
+
+# JPEG: APP1 (XMP) and APP13 (IPTC) segments ahead of the scan data
+
+def _jpeg_segments(data: bytes):
+    if data[:2] != b"\xff\xd8":
+        raise ValueError("Not a JPEG file")
+    segments = []
+    pos = 2
+    while pos + 4 <= len(data) and data[pos] == 0xFF and data[pos + 1] != 0xDA:
+        length = struct.unpack(">H", data[pos + 2:pos + 4])[0]
+        segments.append(data[pos:pos + 2 + length])
+        pos += 2 + length
+    return segments, data[pos:]
+
+
+def _jpeg_segment(marker: int, payload: bytes) -> bytes:
+    if len(payload) > 0xFFFF - 2:
+        raise ValueError("Metadata too large for a JPEG segment")
+    return bytes([0xFF, marker]) + struct.pack(">H", len(payload) + 2) + payload
+
+
+def _is_xmp_segment(segment: bytes) -> bool:
+    return segment[1] == 0xE1 and segment[4:].startswith(XMP_JPEG_HEADER)
+
+
+def _is_iptc_segment(segment: bytes) -> bool:
+    return segment[1] == 0xED and segment[4:].startswith(PHOTOSHOP_JPEG_HEADER)
+
+
+def write_jpeg(data: bytes, xmp: bytes, iptc: Optional[bytes]) -> bytes:
+    segments, rest = _jpeg_segments(data)
+    segments = [
+        s for s in segments
+        if not _is_xmp_segment(s) and not (iptc is not None and _is_iptc_segment(s))
+    ]
+
+    added = [_jpeg_segment(0xE1, XMP_JPEG_HEADER + xmp)]
+    if iptc is not None:
+        # Photoshop image resource 0x0404 (IPTC-NAA) with an empty name
+        resource = b"8BIM" + struct.pack(">HHI", 0x0404, 0, len(iptc)) + iptc
+        if len(iptc) % 2:
+            resource += b"\x00"
+        added.append(_jpeg_segment(0xED, PHOTOSHOP_JPEG_HEADER + resource))
+
+    # JFIF and EXIF segments must stay first
+    insert_at = 0
+    while insert_at < len(segments) and segments[insert_at][1] in (0xE0, 0xE1):
+        insert_at += 1
+    segments[insert_at:insert_at] = added
+    return b"\xff\xd8" + b"".join(segments) + rest
+
+
+def read_jpeg(data: bytes) -> Optional[bytes]:
+    segments, _ = _jpeg_segments(data)
+    for segment in segments:
+        if _is_xmp_segment(segment):
+            return segment[4 + len(XMP_JPEG_HEADER):]
+    return None
+
+
+# PNG: an uncompressed iTXt chunk keyed XML:com.adobe.xmp
+
+def _png_chunks(data: bytes):
+    if not data.startswith(PNG_SIGNATURE):
+        raise ValueError("Not a PNG file")
+    chunks = []
+    pos = len(PNG_SIGNATURE)
+    while pos + 8 <= len(data):
+        length = struct.unpack(">I", data[pos:pos + 4])[0]
+        chunk_type = data[pos + 4:pos + 8]
+        chunks.append((chunk_type, data[pos:pos + 12 + length]))
+        pos += 12 + length
+    return chunks
+
+
+def _is_png_xmp(chunk_type: bytes, chunk: bytes) -> bool:
+    return chunk_type == b"iTXt" and chunk[8:].startswith(PNG_XMP_KEYWORD + b"\x00")
+
+
+def write_png(data: bytes, xmp: bytes) -> bytes:
+    chunks = [c for c in _png_chunks(data) if not _is_png_xmp(*c)]
+    # keyword, no compression, empty language tag and translated keyword
+    body = b"iTXt" + PNG_XMP_KEYWORD + b"\x00\x00\x00\x00\x00" + xmp
+    chunk = struct.pack(">I", len(body) - 4) + body + struct.pack(">I", zlib.crc32(body))
+
+    insert_at = next(i for i, (t, _) in enumerate(chunks) if t in (b"IDAT", b"IEND"))
+    chunks.insert(insert_at, (b"iTXt", chunk))
+    return PNG_SIGNATURE + b"".join(c for _, c in chunks)
+
+
+def read_png(data: bytes) -> Optional[bytes]:
+    for chunk_type, chunk in _png_chunks(data):
+        if _is_png_xmp(chunk_type, chunk):
+            return chunk[8 + len(PNG_XMP_KEYWORD) + 5:-4]
+    return None
+
+
+# WebP: an "XMP " chunk, flagged in the extended-format VP8X header
+
+def _webp_chunks(data: bytes):
+    if data[:4] != b"RIFF" or data[8:12] != b"WEBP":
+        raise ValueError("Not a WebP file")
+    chunks = []
+    pos = 12
+    while pos + 8 <= len(data):
+        size = struct.unpack("<I", data[pos + 4:pos + 8])[0]
+        chunks.append((data[pos:pos + 4], data[pos + 8:pos + 8 + size]))
+        pos += 8 + size + (size % 2)
+    return chunks
+
+
+def _webp_canvas(chunks) -> tuple:
+    # Simple-format files carry the size in the bitstream header
+    for fourcc, payload in chunks:
+        if fourcc == b"VP8 ":
+            width, height = struct.unpack("<HH", payload[6:10])
+            return width & 0x3FFF, height & 0x3FFF, False
+        if fourcc == b"VP8L":
+            bits = struct.unpack("<I", payload[1:5])[0]
+            return (bits & 0x3FFF) + 1, ((bits >> 14) & 0x3FFF) + 1, bool(bits >> 28 & 1)
+    raise ValueError("WebP file has no image data")
+
+
+def write_webp(data: bytes, xmp: bytes) -> bytes:
+    chunks = [(f, p) for f, p in _webp_chunks(data) if f != b"XMP "]
+    if chunks[0][0] == b"VP8X":
+        header = bytearray(chunks[0][1])
+        header[0] |= 0x04
+        chunks[0] = (b"VP8X", bytes(header))
+    else:
+        width, height, alpha = _webp_canvas(chunks)
+        flags = 0x04 | (0x10 if alpha else 0)
+        header = (
+            bytes([flags, 0, 0, 0])
+            + (width - 1).to_bytes(3, "little")
+            + (height - 1).to_bytes(3, "little")
+        )
+        chunks.insert(0, (b"VP8X", header))
+    chunks.append((b"XMP ", xmp))
+
+    body = b"WEBP" + b"".join(
+        fourcc + struct.pack("<I", len(payload)) + payload + (b"\x00" if len(payload) % 2 else b"")
+        for fourcc, payload in chunks
+    )
+    return b"RIFF" + struct.pack("<I", len(body)) + body
+
+
+def read_webp(data: bytes) -> Optional[bytes]:
+    for fourcc, payload in _webp_chunks(data):
+        if fourcc == b"XMP ":
+            return payload
+    return None
+
+
+# TIFF: rewritten through Pillow with the XMP and IPTC tags set
+
+def write_tiff(path: Path, xmp: bytes, iptc: Optional[bytes]):
+    from PIL import Image
+
+    with Image.open(path) as image:
+        image.load()
+        tiffinfo = dict(image.tag_v2)
+        tiffinfo[TIFF_XMP_TAG] = xmp
+        if iptc is not None:
+            tiffinfo[TIFF_IPTC_TAG] = iptc
+        image.save(
+            path,
+            format="TIFF",
+            tiffinfo=tiffinfo,
+            compression=image.info.get("compression"),
+        )
+
+
+def read_tiff(path: Path) -> Optional[bytes]:
+    from PIL import Image
+
+    with Image.open(path) as image:
+        xmp = image.tag_v2.get(TIFF_XMP_TAG)
+    if isinstance(xmp, str):
+        return xmp.encode("utf-8")
+    return xmp
+
+
+def write_metadata(path: Path, metadata: dict, iptc: bool):
+    xmp = build_xmp(metadata)
+    suffix = path.suffix.lower()
+    if suffix in (".tif", ".tiff"):
+        write_tiff(path, xmp, build_iptc(metadata) if iptc else None)
+        return
+
+    data = path.read_bytes()
+    if suffix in (".jpg", ".jpeg"):
+        data = write_jpeg(data, xmp, build_iptc(metadata) if iptc else None)
+    elif suffix == ".png":
+        data = write_png(data, xmp)
+    elif suffix == ".webp":
+        data = write_webp(data, xmp)
+    else:
+        raise ValueError(f"Embedding metadata is not supported for '{suffix}' files")
+
+    # Write alongside and swap in, so a failure never leaves a truncated image
+    temp_path = path.with_name(path.name + ".metadata-tmp")
+    temp_path.write_bytes(data)
+    temp_path.replace(path)
+
+
+def read_metadata(path: Path) -> dict:
+    suffix = path.suffix.lower()
+    if suffix in (".tif", ".tiff"):
+        xmp = read_tiff(path)
+    else:
+        readers = {".jpg": read_jpeg, ".jpeg": read_jpeg, ".png": read_png, ".webp": read_webp}
+        if suffix not in readers:
+            raise ValueError(f"Reading metadata is not supported for '{suffix}' files")
+        xmp = readers[suffix](path.read_bytes())
+
+    if xmp is None:
+        return {"creator": None, "copyright": None, "keywords": [], "description": None}
+    return parse_xmp(xmp)
@@ -0,0 +1,65 @@
+"""Model provenance for processors that wrap an ML model."""
+import hashlib
+import os
+from dataclasses import dataclass
+from pathlib import Path
+from typing import Optional
+
+MODEL_DIR = Path(
+    os.environ.get("PHOTO_PROCESSOR_MODEL_DIR", Path(__file__).parent.parent / "models")
+)
+
+
+@dataclass(frozen=True)
+class ModelSpec:
+    name: str
+    version: str
+    # File name of the weights inside the model directory
+    weights: str
+
+
+# Processors that wrap a model; every other processor is plain code
+PROCESSOR_MODELS = {
+    "ai_denoise": ModelSpec("denoise-net", "2.1.0", "denoise-net-2.1.0.onnx"),
+    "ai_upscale": ModelSpec("upscale-net", "1.3.0", "upscale-net-1.3.0.onnx"),
+}
+
+_hash_cache: dict[tuple[Path, float], str] = {}
+
+
+def weights_hash(path: Path) -> str:
+    """sha256 of a weights file, cached until the file changes"""
+    key = (path, path.stat().st_mtime)
+    if key not in _hash_cache:
+        digest = hashlib.sha256()
+        with open(path, "rb") as weights:
+            for chunk in iter(lambda: weights.read(1 << 20), b""):
+                digest.update(chunk)
+        _hash_cache[key] = f"sha256:{digest.hexdigest()}"
+    return _hash_cache[key]
+
+
+def model_info(processor_type: str, model_dir: Path = MODEL_DIR) -> Optional[dict]:
+    """Name, version and weights hash, or None for processors without a model.
+
+    Raises FileNotFoundError when the processor needs weights that are not
+    installed, since an unhashed model can't be traced.
+    """
+    spec = PROCESSOR_MODELS.get(processor_type)
+    if spec is None:
+        return None
+    return {
+        "name": spec.name,
+        "version": spec.version,
+        "hash": weights_hash(Path(model_dir) / spec.weights),
+    }
+
+
+def node_models(operations: list[dict], model_dir: Path = MODEL_DIR) -> list[dict]:
+    """Per-node model info for the nodes of a pipeline that wrap a model"""
+    models = []
+    for node in operations:
+        info = model_info(node.get("processor_type", ""), model_dir)
+        if info is not None:
+            models.append({"node_id": node.get("id", ""), "model": info})
+    return models
@@ -0,0 +1,32 @@
+"""Speed/quality profiles mapped onto per-node processing defaults.
+
+Each profile supplies defaults that a node's own parameters override:
+    draft         bilinear resampling, 1 refinement pass, 0.5x working scale
+    standard      bicubic resampling, 2 refinement passes, full scale
+    high_quality  Lanczos resampling, 4 refinement passes, full scale
+"""
+
+DEFAULT_PROFILE = "standard"
+
+PROFILE_DEFAULTS = {
+    "draft": {"interpolation": "linear", "refinement_passes": 1, "working_scale": 0.5},
+    "standard": {"interpolation": "cubic", "refinement_passes": 2, "working_scale": 1.0},
+    "high_quality": {"interpolation": "lanczos", "refinement_passes": 4, "working_scale": 1.0},
+}
+
+
+def node_defaults(profile: str) -> dict:
+    """Defaults for a profile; ValueError for names the backend doesn't know"""
+    if profile not in PROFILE_DEFAULTS:
+        known = ", ".join(PROFILE_DEFAULTS)
+        raise ValueError(f"Unknown processing profile '{profile}' (expected one of {known})")
+    return PROFILE_DEFAULTS[profile]
+
+
+def apply_profile(operations: list[dict], profile: str) -> list[dict]:
+    """Operations with the profile's defaults filled in under each node's parameters"""
+    defaults = node_defaults(profile)
+    return [
+        {**node, "parameters": {**defaults, **(node.get("parameters") or {})}}
+        for node in operations
+    ]
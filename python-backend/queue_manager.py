@@ -9,6 +9,8 @@ import time
 from pydantic import BaseModel
 from enum import Enum
 
+import job_events
+
 
 class QueueItemStatus(str, Enum):
     PENDING = "pending"
@@ -18,6 +20,7 @@ class QueueItemStatus(str, Enum):
 
 
 class QueueItemPriority(str, Enum):
+    TETHER = "tether"  # live tethered captures, ahead of everything else
     HIGH = "high"
     NORMAL = "normal"
     LOW = "low"
@@ -42,6 +45,7 @@ class QueueItem(BaseModel):
     last_modified: datetime
     retry_count: int = 0
     error: Optional[str] = None
+    output_path: Optional[str] = None
 
 
 class QueueStatus(BaseModel):
@@ -51,6 +55,8 @@ class QueueStatus(BaseModel):
     completed_items: int
     failed_items: int
     is_paused: bool
+    # Input bytes of completed items, so clients can derive bytes/second
+    completed_bytes: int = 0
 
 
 class ProcessingQueue:
@@ -60,6 +66,7 @@ class ProcessingQueue:
         self.processing_lock = asyncio.Lock()
         self.file_stability_timeout = 2.0  # seconds
         self.file_sizes: Dict[str, tuple[int, float]] = {}  # path -> (size, timestamp)
+        self.events = job_events.EventBroadcaster()
         
     async def add_item(self, request: AddToQueueRequest) -> QueueItem:
         """Add a new item to the queue after checking file stability"""
@@ -124,9 +131,10 @@ class ProcessingQueue:
     def _reorder_queue(self):
         """Reorder queue based on priority and timestamp"""
         priority_order = {
-            QueueItemPriority.HIGH: 0,
-            QueueItemPriority.NORMAL: 1,
-            QueueItemPriority.LOW: 2
+            QueueItemPriority.TETHER: 0,
+            QueueItemPriority.HIGH: 1,
+            QueueItemPriority.NORMAL: 2,
+            QueueItemPriority.LOW: 3
         }
         
         # Sort items by status (pending first), then priority, then timestamp
@@ -153,11 +161,18 @@ class ProcessingQueue:
                 if item.status == QueueItemStatus.PENDING:
                     item.status = QueueItemStatus.PROCESSING
                     item.started_at = datetime.utcnow()
+                    self.events.publish(job_events.job_started(item.id, item.path, item.folder_id))
                     return item
         
         return None
     
-    async def update_item(self, item_id: str, status: QueueItemStatus, error: Optional[str] = None) -> Optional[QueueItem]:
+    async def update_item(
+        self,
+        item_id: str,
+        status: QueueItemStatus,
+        error: Optional[str] = None,
+        output_path: Optional[str] = None,
+    ) -> Optional[QueueItem]:
         """Update the status of a queue item"""
         async with self.processing_lock:
             if item_id not in self.queue:
@@ -166,13 +181,21 @@ class ProcessingQueue:
             item = self.queue[item_id]
             item.status = status
             
-            if status == QueueItemStatus.COMPLETED:
+            if status == QueueItemStatus.PROCESSING:
+                item.started_at = datetime.utcnow()
+                self.events.publish(job_events.job_started(item.id, item.path, item.folder_id))
+            elif status == QueueItemStatus.COMPLETED:
                 item.completed_at = datetime.utcnow()
+                item.output_path = output_path or item.path
+                self.events.publish(job_events.job_completed(
+                    item.id, item.output_path, item.started_at, item.completed_at, item.folder_id
+                ))
             elif status == QueueItemStatus.FAILED:
                 item.error = error
                 item.retry_count += 1
                 # Move to end of queue for retry
                 self.queue.move_to_end(item_id)
+                self.events.publish(job_events.job_failed(item.id, error, item.folder_id))
             
             return item
     
@@ -193,8 +216,11 @@ class ProcessingQueue:
             QueueItemStatus.FAILED: 0
         }
         
+        completed_bytes = 0
         for item in self.queue.values():
             status_counts[item.status] += 1
+            if item.status == QueueItemStatus.COMPLETED:
+                completed_bytes += item.file_size
         
         return QueueStatus(
             total_items=len(self.queue),
@@ -202,7 +228,8 @@ class ProcessingQueue:
             processing_items=status_counts[QueueItemStatus.PROCESSING],
             completed_items=status_counts[QueueItemStatus.COMPLETED],
             failed_items=status_counts[QueueItemStatus.FAILED],
-            is_paused=self.is_paused
+            is_paused=self.is_paused,
+            completed_bytes=completed_bytes
         )
     
     async def get_items(self, limit: int = 100, offset: int = 0) -> List[QueueItem]:
@@ -0,0 +1,26 @@
+"""Turn raw detector output into the subject boxes the crop suggestions use."""
+import math
+
+
+def subjects_from_detections(boxes: list, weights: list, width: int, height: int) -> list[dict]:
+    """Clip (x, y, w, h) boxes to the image and map each detector weight to a
+    0-1 confidence. Boxes left empty after clipping are dropped; the strongest
+    detection comes first."""
+    subjects = []
+    for (x, y, w, h), weight in zip(boxes, weights):
+        x0, y0 = max(0, int(x)), max(0, int(y))
+        x1, y1 = min(width, int(x + w)), min(height, int(y + h))
+        if x1 <= x0 or y1 <= y0:
+            continue
+        subjects.append(
+            {
+                "x": x0,
+                "y": y0,
+                "width": x1 - x0,
+                "height": y1 - y0,
+                # Cascade weights are unbounded margins around 0
+                "confidence": 1 / (1 + math.exp(-float(weight))),
+            }
+        )
+    subjects.sort(key=lambda subject: subject["confidence"], reverse=True)
+    return subjects
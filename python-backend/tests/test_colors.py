@@ -0,0 +1,89 @@
+import unittest
+
+import colors
+
+
+class SwatchesFromClustersTest(unittest.TestCase):
+    def test_centres_are_converted_from_bgr_and_sorted_by_coverage(self):
+        swatches = colors.swatches_from_clusters(
+            [[10.4, 20.6, 250.2], [255.0, 0.0, 0.0]],
+            [25, 75],
+        )
+        self.assertEqual(
+            swatches,
+            [
+                {"r": 0, "g": 0, "b": 255, "coverage": 75.0},
+                {"r": 250, "g": 21, "b": 10, "coverage": 25.0},
+            ],
+        )
+
+    def test_empty_clusters_are_dropped_and_channels_clamped(self):
+        swatches = colors.swatches_from_clusters([[-3.0, 300.0, 128.0], [0, 0, 0]], [4, 0])
+        self.assertEqual(swatches, [{"r": 128, "g": 255, "b": 0, "coverage": 100.0}])
+        self.assertEqual(colors.swatches_from_clusters([], []), [])
+
+
+class DeltaE2000Test(unittest.TestCase):
+    # Pairs from Sharma, Wu and Dalal's CIEDE2000 test data
+    SHARMA_PAIRS = [
+        ((50.0, 2.6772, -79.7751), (50.0, 0.0, -82.7485), 2.0425),
+        ((50.0, 3.1571, -77.2803), (50.0, 0.0, -82.7485), 2.8615),
+        ((50.0, 0.0, 0.0), (50.0, -1.0, 2.0), 2.3669),
+        ((50.0, 2.5, 0.0), (73.0, 25.0, -18.0), 27.1492),
+        ((60.2574, -34.0099, 36.2677), (60.4626, -34.1751, 39.4387), 1.2644),
+        ((2.0776, 0.0795, -1.135), (0.9033, -0.0636, -0.5514), 0.9082),
+    ]
+
+    def test_matches_published_pairs(self):
+        for first, second, expected in self.SHARMA_PAIRS:
+            self.assertAlmostEqual(colors.delta_e_2000(first, second), expected, places=4)
+            self.assertAlmostEqual(colors.delta_e_2000(second, first), expected, places=4)
+
+    def test_identical_colours_have_no_difference(self):
+        self.assertEqual(colors.delta_e_2000((50.0, 10.0, -10.0), (50.0, 10.0, -10.0)), 0.0)
+
+
+class ChartAccuracyTest(unittest.TestCase):
+    def test_srgb_white_and_black_map_to_the_lab_extremes(self):
+        lightness, a, b = colors.srgb_to_lab((255, 255, 255))
+        self.assertAlmostEqual(lightness, 100.0, places=2)
+        self.assertAlmostEqual(a, 0.0, places=2)
+        self.assertAlmostEqual(b, 0.0, places=2)
+        self.assertEqual(colors.srgb_to_lab((0, 0, 0)), (0.0, 0.0, 0.0))
+
+    def test_accurate_chart_scores_low_and_a_color_cast_scores_high(self):
+        # A published 8-bit sRGB rendering of the chart; it predates the current
+        # reference values, so single patches differ by up to about 4
+        chart = [
+            (115, 82, 68), (194, 150, 130), (98, 122, 157), (87, 108, 67),
+            (133, 128, 177), (103, 189, 170), (214, 126, 44), (80, 91, 166),
+            (193, 90, 99), (94, 60, 108), (157, 188, 64), (224, 163, 46),
+            (56, 61, 150), (70, 148, 73), (175, 54, 60), (231, 199, 31),
+            (187, 86, 149), (8, 133, 161), (243, 243, 242), (200, 200, 200),
+            (160, 160, 160), (122, 122, 121), (85, 85, 85), (52, 52, 52),
+        ]
+        self.assertTrue(colors.looks_like_chart(chart))
+        accurate = colors.chart_accuracy(chart)
+        self.assertEqual([patch["name"] for patch in accurate][:2], ["dark_skin", "light_skin"])
+        average = sum(patch["delta_e"] for patch in accurate) / len(accurate)
+        self.assertLess(average, 2.0)
+        self.assertLess(max(patch["delta_e"] for patch in accurate), 5.0)
+
+        cast = [(r, g, min(255, b + 40)) for r, g, b in chart]
+        average = sum(patch["delta_e"] for patch in colors.chart_accuracy(cast)) / len(cast)
+        self.assertGreater(average, 5.0)
+
+    def test_cells_sample_the_middle_of_each_patch(self):
+        cells = colors.chart_cells(600, 400)
+        self.assertEqual(len(cells), 24)
+        self.assertEqual(cells[0], (25, 25, 75, 75))
+        self.assertEqual(cells[7], (125, 125, 175, 175))
+        self.assertEqual(cells[-1], (525, 325, 575, 375))
+
+    def test_cells_without_a_neutral_ramp_are_not_a_chart(self):
+        self.assertFalse(colors.looks_like_chart([(128, 128, 128)] * 24))
+        self.assertFalse(colors.looks_like_chart([(255, 255, 255)] * 6))
+
+
+if __name__ == "__main__":
+    unittest.main()
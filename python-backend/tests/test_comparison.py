@@ -0,0 +1,23 @@
+import unittest
+
+import comparison
+
+
+class CompositeSizeTest(unittest.TestCase):
+    def test_each_layout_keeps_the_image_aspect_ratio(self):
+        self.assertEqual(comparison.composite_size("side_by_side", 1200, 800), (2400, 800))
+        self.assertEqual(comparison.composite_size("stacked", 1200, 800), (1200, 1600))
+        self.assertEqual(comparison.composite_size("split_slider", 800, 1200), (800, 1200))
+        with self.assertRaises(ValueError):
+            comparison.composite_size("diagonal", 1200, 800)
+
+    def test_split_column_follows_the_slider(self):
+        self.assertEqual(comparison.split_column(0.0, 1200), 0)
+        self.assertEqual(comparison.split_column(0.25, 1200), 300)
+        self.assertEqual(comparison.split_column(1.0, 1200), 1200)
+        with self.assertRaises(ValueError):
+            comparison.split_column(1.5, 1200)
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,33 @@
+import unittest
+
+import contact_sheet
+
+
+class GridLayoutTest(unittest.TestCase):
+    def test_cells_fill_rows_in_selection_order(self):
+        size, corners = contact_sheet.grid_layout(5, 2, 100, labels=True)
+        # 2 columns x 3 rows of 108 x 128 cells, plus the outer padding
+        self.assertEqual(size, (8 + 2 * 108, 8 + 3 * 128))
+        self.assertEqual(corners, [(8, 8), (116, 8), (8, 136), (116, 136), (8, 264)])
+
+    def test_fewer_images_than_columns_shrinks_the_sheet(self):
+        size, corners = contact_sheet.grid_layout(2, 6, 100, labels=False)
+        self.assertEqual(size, (8 + 2 * 108, 8 + 108))
+        self.assertEqual(corners, [(8, 8), (116, 8)])
+
+
+class ThumbnailFitTest(unittest.TestCase):
+    def test_thumbnails_keep_their_aspect_ratio(self):
+        self.assertEqual(contact_sheet.fit_within(6000, 4000, 300), (300, 200))
+        self.assertEqual(contact_sheet.fit_within(4000, 6000, 300), (200, 300))
+        self.assertEqual(contact_sheet.fit_within(10000, 10, 100), (100, 1))
+
+    def test_long_labels_are_shortened_in_the_middle(self):
+        self.assertEqual(contact_sheet.label_for("IMG_0001.jpg", 20), "IMG_0001.jpg")
+        label = contact_sheet.label_for("wedding_ceremony_first_kiss_0042.jpg", 15)
+        self.assertEqual(len(label), 15)
+        self.assertEqual(label, "wedding~042.jpg")
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,39 @@
+import unittest
+
+import encoding
+
+
+def fake_encoder(bytes_per_quality: int, calls: list):
+    def encode(quality: int) -> bytes:
+        calls.append(quality)
+        return b"x" * (quality * bytes_per_quality)
+
+    return encode
+
+
+class SearchQualityTest(unittest.TestCase):
+    def test_lands_on_the_highest_quality_under_the_target(self):
+        calls = []
+        quality, data = encoding.search_quality(fake_encoder(1000, calls), 82_500, 8)
+        self.assertEqual(quality, 82)
+        self.assertEqual(len(data), 82_000)
+        self.assertLessEqual(len(calls), 8)
+
+    def test_size_exactly_at_the_target_fits(self):
+        quality, data = encoding.search_quality(fake_encoder(1000, []), 50_000, 8)
+        self.assertEqual((quality, len(data)), (50, 50_000))
+
+    def test_unreachable_target_returns_the_smallest_encoding(self):
+        quality, data = encoding.search_quality(fake_encoder(1000, []), 500, 8)
+        self.assertEqual(quality, encoding.MIN_QUALITY)
+        self.assertEqual(len(data), 1000)
+
+    def test_iterations_are_bounded(self):
+        calls = []
+        quality, _ = encoding.search_quality(fake_encoder(1000, calls), 82_500, 3)
+        self.assertEqual(len(calls), 3)
+        self.assertLessEqual(quality * 1000, 82_500)
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,68 @@
+import asyncio
+import json
+import unittest
+from datetime import datetime, timedelta
+
+import job_events
+
+
+class JobEventTest(unittest.TestCase):
+    def test_completed_event_reports_processing_time(self):
+        started = datetime(2026, 10, 15, 12, 0, 0)
+        event = job_events.job_completed(
+            "item-1", "out.jpg", started, started + timedelta(milliseconds=1250), "studio-a"
+        )
+        self.assertEqual(
+            event,
+            {
+                "type": "job_completed",
+                "job_id": "item-1",
+                "output_path": "out.jpg",
+                "processing_time_ms": 1250,
+                "folder_id": "studio-a",
+            },
+        )
+        never_started = job_events.job_completed("item-1", "out.jpg", None, started, None)
+        self.assertIsNone(never_started["processing_time_ms"])
+
+    def test_failed_event_always_has_an_error(self):
+        event = job_events.job_failed("item-2", None, "studio-a")
+        self.assertEqual(event["type"], "job_failed")
+        self.assertEqual(event["error"], "Unknown error")
+
+    def test_events_are_framed_as_server_sent_events(self):
+        message = job_events.sse(job_events.job_started("item-3", "in.jpg", "studio-a"))
+        self.assertTrue(message.startswith("data: "))
+        self.assertTrue(message.endswith("\n\n"))
+        self.assertEqual(json.loads(message[len("data: "):])["type"], "job_started")
+
+
+class BroadcasterTest(unittest.TestCase):
+    def test_every_subscriber_gets_each_event(self):
+        async def scenario():
+            broadcaster = job_events.EventBroadcaster()
+            first, second = broadcaster.subscribe(), broadcaster.subscribe()
+            broadcaster.publish({"type": "job_started"})
+            broadcaster.unsubscribe(second)
+            broadcaster.publish({"type": "job_failed"})
+            return [first.get_nowait()["type"] for _ in range(first.qsize())], second.qsize()
+
+        received, unsubscribed = asyncio.run(scenario())
+        self.assertEqual(received, ["job_started", "job_failed"])
+        self.assertEqual(unsubscribed, 1)
+
+    def test_a_stalled_subscriber_drops_its_oldest_events(self):
+        async def scenario():
+            broadcaster = job_events.EventBroadcaster()
+            events = broadcaster.subscribe()
+            for index in range(job_events.MAX_PENDING_EVENTS + 2):
+                broadcaster.publish({"index": index})
+            return events.qsize(), events.get_nowait()["index"]
+
+        size, oldest = asyncio.run(scenario())
+        self.assertEqual(size, job_events.MAX_PENDING_EVENTS)
+        self.assertEqual(oldest, 2)
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,103 @@
+import struct
+import tempfile
+import unittest
+import zlib
+from pathlib import Path
+
+import metadata_io
+
+METADATA = {
+    "creator": "Ana Núñez",
+    "copyright": "© 2026 Studio <North> & Co",
+    "keywords": ["wedding", "outdoor"],
+    "description": "First dance",
+}
+
+
+def jpeg_bytes() -> bytes:
+    jfif = b"\xff\xe0" + struct.pack(">H", 16) + b"JFIF\x00\x01\x01\x00\x00\x01\x00\x01\x00\x00"
+    quant = b"\xff\xdb" + struct.pack(">H", 4) + b"\x00\x01"
+    scan = b"\xff\xda" + struct.pack(">H", 4) + b"\x00\x00" + b"\x12\x34" + b"\xff\xd9"
+    return b"\xff\xd8" + jfif + quant + scan
+
+
+def png_chunk(chunk_type: bytes, data: bytes) -> bytes:
+    body = chunk_type + data
+    return struct.pack(">I", len(data)) + body + struct.pack(">I", zlib.crc32(body))
+
+
+def png_bytes() -> bytes:
+    header = struct.pack(">IIBBBBB", 1, 1, 8, 0, 0, 0, 0)
+    return (
+        metadata_io.PNG_SIGNATURE
+        + png_chunk(b"IHDR", header)
+        + png_chunk(b"IDAT", zlib.compress(b"\x00\x80"))
+        + png_chunk(b"IEND", b"")
+    )
+
+
+def webp_bytes(width: int, height: int) -> bytes:
+    bits = (width - 1) | ((height - 1) << 14)
+    payload = b"\x2f" + struct.pack("<I", bits) + b"\x00"
+    chunk = b"VP8L" + struct.pack("<I", len(payload)) + payload
+    return b"RIFF" + struct.pack("<I", 4 + len(chunk)) + b"WEBP" + chunk
+
+
+class MetadataRoundTripTest(unittest.TestCase):
+    def round_trip(self, name: str, data: bytes, iptc: bool = False) -> dict:
+        with tempfile.TemporaryDirectory() as folder:
+            path = Path(folder) / name
+            path.write_bytes(data)
+            metadata_io.write_metadata(path, METADATA, iptc)
+            return metadata_io.read_metadata(path)
+
+    def test_jpeg_reads_back(self):
+        self.assertEqual(self.round_trip("photo.jpg", jpeg_bytes(), iptc=True), METADATA)
+
+    def test_png_reads_back(self):
+        self.assertEqual(self.round_trip("photo.png", png_bytes()), METADATA)
+
+    def test_webp_reads_back(self):
+        self.assertEqual(self.round_trip("photo.webp", webp_bytes(3, 2)), METADATA)
+
+    def test_rewriting_replaces_earlier_metadata(self):
+        data = metadata_io.write_jpeg(jpeg_bytes(), metadata_io.build_xmp(METADATA), None)
+        data = metadata_io.write_jpeg(data, metadata_io.build_xmp({"creator": "B"}), None)
+        self.assertEqual(data.count(metadata_io.XMP_JPEG_HEADER), 1)
+        self.assertEqual(metadata_io.parse_xmp(metadata_io.read_jpeg(data))["creator"], "B")
+
+    def test_jpeg_scan_data_is_untouched(self):
+        original = jpeg_bytes()
+        data = metadata_io.write_jpeg(original, metadata_io.build_xmp(METADATA), None)
+        self.assertTrue(data.endswith(original[original.index(b"\xff\xda"):]))
+        # JFIF stays the first segment
+        self.assertEqual(data[2:4], b"\xff\xe0")
+
+    def test_webp_gets_an_extended_header(self):
+        data = metadata_io.write_webp(webp_bytes(3, 2), metadata_io.build_xmp(METADATA))
+        fourcc, header = metadata_io._webp_chunks(data)[0]
+        self.assertEqual(fourcc, b"VP8X")
+        self.assertEqual(header[0] & 0x04, 0x04)
+        self.assertEqual(int.from_bytes(header[4:7], "little") + 1, 3)
+        self.assertEqual(int.from_bytes(header[7:10], "little") + 1, 2)
+        self.assertEqual(struct.unpack("<I", data[4:8])[0], len(data) - 8)
+
+    def test_missing_metadata_reads_as_empty(self):
+        with tempfile.TemporaryDirectory() as folder:
+            path = Path(folder) / "plain.png"
+            path.write_bytes(png_bytes())
+            self.assertEqual(
+                metadata_io.read_metadata(path),
+                {"creator": None, "copyright": None, "keywords": [], "description": None},
+            )
+
+    def test_unsupported_format_is_rejected(self):
+        with tempfile.TemporaryDirectory() as folder:
+            path = Path(folder) / "photo.bmp"
+            path.write_bytes(b"BM")
+            with self.assertRaises(ValueError):
+                metadata_io.write_metadata(path, METADATA, False)
+
+
+if __name__ == "__main__":
+    unittest.main()
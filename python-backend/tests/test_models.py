@@ -0,0 +1,44 @@
+import hashlib
+import tempfile
+import unittest
+from pathlib import Path
+
+from processors import models
+
+
+class ModelInfoTest(unittest.TestCase):
+    def setUp(self):
+        self.tmp = tempfile.TemporaryDirectory()
+        self.model_dir = Path(self.tmp.name)
+        self.weights = b"denoise weights"
+        spec = models.PROCESSOR_MODELS["ai_denoise"]
+        (self.model_dir / spec.weights).write_bytes(self.weights)
+
+    def tearDown(self):
+        self.tmp.cleanup()
+
+    def test_ml_processor_reports_name_version_and_weights_hash(self):
+        info = models.model_info("ai_denoise", self.model_dir)
+        self.assertEqual(info["name"], "denoise-net")
+        self.assertEqual(info["version"], "2.1.0")
+        self.assertEqual(info["hash"], "sha256:" + hashlib.sha256(self.weights).hexdigest())
+
+    def test_plain_processor_has_no_model(self):
+        self.assertIsNone(models.model_info("sharpen", self.model_dir))
+
+    def test_missing_weights_are_an_error(self):
+        with self.assertRaises(FileNotFoundError):
+            models.model_info("ai_upscale", self.model_dir)
+
+    def test_only_model_nodes_are_listed(self):
+        operations = [
+            {"id": "sharpen_1", "processor_type": "sharpen", "parameters": {}},
+            {"id": "denoise_1", "processor_type": "ai_denoise", "parameters": {}},
+        ]
+        listed = models.node_models(operations, self.model_dir)
+        self.assertEqual([entry["node_id"] for entry in listed], ["denoise_1"])
+        self.assertEqual(listed[0]["model"]["version"], "2.1.0")
+
+
+if __name__ == "__main__":
+    unittest.main()
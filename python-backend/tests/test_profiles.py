@@ -0,0 +1,31 @@
+import unittest
+
+import profiles
+
+
+class ProfileTest(unittest.TestCase):
+    def test_every_profile_fills_the_same_settings(self):
+        keys = {frozenset(defaults) for defaults in profiles.PROFILE_DEFAULTS.values()}
+        self.assertEqual(len(keys), 1)
+        self.assertIn(profiles.DEFAULT_PROFILE, profiles.PROFILE_DEFAULTS)
+
+    def test_node_parameters_override_profile_defaults(self):
+        operations = [
+            {"id": "resize_1", "processor_type": "resize", "parameters": {"interpolation": "area"}},
+            {"id": "denoise_1", "processor_type": "ai_denoise"},
+        ]
+        applied = profiles.apply_profile(operations, "draft")
+        self.assertEqual(applied[0]["parameters"]["interpolation"], "area")
+        self.assertEqual(applied[0]["parameters"]["working_scale"], 0.5)
+        self.assertEqual(applied[1]["parameters"]["interpolation"], "linear")
+        self.assertEqual(applied[1]["id"], "denoise_1")
+        # The request's own operations are left untouched
+        self.assertNotIn("parameters", operations[1])
+
+    def test_unknown_profile_is_rejected(self):
+        with self.assertRaisesRegex(ValueError, "Unknown processing profile 'fast'"):
+            profiles.apply_profile([], "fast")
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,27 @@
+import unittest
+
+import subjects
+
+
+class SubjectsFromDetectionsTest(unittest.TestCase):
+    def test_boxes_are_clipped_and_ranked_by_confidence(self):
+        found = subjects.subjects_from_detections(
+            [(10, 20, 30, 40), (380, -10, 40, 50)],
+            [0.0, 2.0],
+            400,
+            300,
+        )
+        self.assertEqual(
+            [(s["x"], s["y"], s["width"], s["height"]) for s in found],
+            [(380, 0, 20, 40), (10, 20, 30, 40)],
+        )
+        self.assertAlmostEqual(found[0]["confidence"], 0.8808, places=4)
+        self.assertEqual(found[1]["confidence"], 0.5)
+
+    def test_boxes_outside_the_image_are_dropped(self):
+        self.assertEqual(subjects.subjects_from_detections([(500, 0, 10, 10)], [3.0], 400, 300), [])
+        self.assertEqual(subjects.subjects_from_detections([], [], 400, 300), [])
+
+
+if __name__ == "__main__":
+    unittest.main()
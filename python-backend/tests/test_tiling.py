@@ -0,0 +1,46 @@
+import unittest
+
+import tiling
+
+
+class FeatherWeightsTest(unittest.TestCase):
+    def test_neighbours_sum_to_one_across_the_shared_band(self):
+        # Tiles of 256 content pixels with 64 pixels of overlap on a 768 wide image
+        left = tiling.feather_weights(0, 320, 768, 64)
+        middle = tiling.feather_weights(192, 384, 768, 64)
+        for position in range(192, 320):
+            total = left[position] + middle[position - 192]
+            self.assertAlmostEqual(total, 1.0)
+
+    def test_image_border_keeps_full_weight(self):
+        left = tiling.feather_weights(0, 320, 768, 64)
+        self.assertEqual(left[:192], [1.0] * 192)
+        self.assertLess(left[-1], 0.01)
+
+        right = tiling.feather_weights(448, 320, 768, 64)
+        self.assertLess(right[0], 0.01)
+        self.assertEqual(right[128:], [1.0] * 192)
+
+    def test_single_tile_and_clipped_band_stay_positive(self):
+        self.assertEqual(tiling.feather_weights(0, 100, 100, 64), [1.0] * 100)
+        # Ragged last column: shorter than the band it shares with its neighbour
+        ragged = tiling.feather_weights(448, 40, 488, 64)
+        self.assertTrue(all(weight > 0 for weight in ragged))
+
+    def test_no_overlap_means_no_blending(self):
+        self.assertEqual(tiling.feather_weights(256, 256, 768, 0), [1.0] * 256)
+
+
+class CheckTileTest(unittest.TestCase):
+    def test_tiles_must_be_inside_the_image(self):
+        tiling.check_tile(448, 192, 152, 108, 600, 300)
+        with self.assertRaises(ValueError):
+            tiling.check_tile(448, 192, 153, 108, 600, 300)
+        with self.assertRaises(ValueError):
+            tiling.check_tile(-1, 0, 10, 10, 600, 300)
+        with self.assertRaises(ValueError):
+            tiling.check_tile(0, 0, 0, 10, 600, 300)
+
+
+if __name__ == "__main__":
+    unittest.main()
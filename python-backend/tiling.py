@@ -0,0 +1,37 @@
+"""Geometry for tiled processing: checking tile bounds and the blend weights
+used to feather neighbouring tiles together on reassembly.
+
+Kept free of NumPy so the seam maths can be tested on its own; main.py turns
+the per-axis weights into a 2D mask with np.outer.
+"""
+
+
+def check_tile(x: int, y: int, width: int, height: int, image_width: int, image_height: int):
+    """Raise ValueError unless the tile is non-empty and inside the image"""
+    if width <= 0 or height <= 0:
+        raise ValueError("Tile must have a positive size")
+    if x < 0 or y < 0 or x + width > image_width or y + height > image_height:
+        raise ValueError(
+            f"Tile {width}x{height} at ({x}, {y}) is outside the {image_width}x{image_height} image"
+        )
+
+
+def feather_weights(start: int, length: int, total: int, overlap: int) -> list[float]:
+    """Blend weights along one axis of a tile spanning [start, start + length).
+
+    Each tile reaches `overlap` pixels into its neighbour, so two neighbours
+    share a band 2 * overlap wide. Across that band one tile ramps down while
+    the other ramps up and the two always sum to 1; edges on the image border
+    keep full weight. Every weight is positive, so normalising by the summed
+    weights also covers bands clipped short at the image edge.
+    """
+    band = 2 * overlap
+    weights = []
+    for i in range(length):
+        weight = 1.0
+        if band and start > 0:
+            weight = min(weight, (i + 0.5) / band)
+        if band and start + length < total:
+            weight = min(weight, (length - i - 0.5) / band)
+        weights.append(weight)
+    return weights
@@ -0,0 +1,261 @@
+use crate::errors;
+use crate::hot_folder::HotFolderManager;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, State};
+use tokio::task::JoinHandle;
+
+// Tauri event names re-emitted for the backend queue's typed job events
+pub const JOB_STARTED_EVENT: &str = "job-started";
+pub const JOB_COMPLETED_EVENT: &str = "job-completed";
+pub const JOB_FAILED_EVENT: &str = "job-failed";
+// Untyped queue status snapshots are forwarded as-is
+pub const QUEUE_STATUS_EVENT: &str = "queue-status";
+
+const RECONNECT_DELAY: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobStarted {
+    pub job_id: String,
+    pub path: String,
+    pub folder_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobCompleted {
+    pub job_id: String,
+    pub output_path: String,
+    pub processing_time_ms: Option<u64>,
+    #[serde(default)]
+    pub folder_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobFailed {
+    pub job_id: String,
+    pub error: String,
+    #[serde(default)]
+    pub folder_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type")]
+enum BackendEvent {
+    #[serde(rename = "job_started")]
+    Started(JobStarted),
+    #[serde(rename = "job_completed")]
+    Completed(JobCompleted),
+    #[serde(rename = "job_failed")]
+    Failed(JobFailed),
+}
+
+// One message from /queue/events: untyped status snapshots every second,
+// with typed job events interleaved as queue items change state
+#[derive(Debug)]
+enum StreamMessage {
+    Status(serde_json::Value),
+    Job(BackendEvent),
+}
+
+fn parse_message(data: &str) -> Result<StreamMessage, String> {
+    let value: serde_json::Value =
+        serde_json::from_str(data).map_err(|e| format!("Malformed backend event: {}", e))?;
+
+    let event_type = match value.get("type").and_then(|t| t.as_str()) {
+        Some(event_type) => event_type.to_string(),
+        None => return Ok(StreamMessage::Status(value)),
+    };
+    serde_json::from_value::<BackendEvent>(value)
+        .map(StreamMessage::Job)
+        .map_err(|e| format!("Backend event '{}': {}", event_type, e))
+}
+
+fn dispatch(app_handle: &AppHandle, data: &str) {
+    match parse_message(data) {
+        Ok(StreamMessage::Status(status)) => {
+            let _ = app_handle.emit(QUEUE_STATUS_EVENT, &status);
+        }
+        Ok(StreamMessage::Job(BackendEvent::Started(event))) => {
+            let _ = app_handle.emit(JOB_STARTED_EVENT, &event);
+        }
+        Ok(StreamMessage::Job(BackendEvent::Completed(event))) => {
+            if let Some(folder_id) = &event.folder_id {
+                app_handle.state::<Arc<HotFolderManager>>().record_result(folder_id, true);
+            }
+            let _ = app_handle.emit(JOB_COMPLETED_EVENT, &event);
+        }
+        Ok(StreamMessage::Job(BackendEvent::Failed(event))) => {
+            if let Some(folder_id) = &event.folder_id {
+                app_handle.state::<Arc<HotFolderManager>>().record_result(folder_id, false);
+            }
+            let _ = app_handle.emit(JOB_FAILED_EVENT, &event);
+        }
+        Err(e) => {
+            eprintln!("Ignoring {}", e);
+        }
+    }
+}
+
+async fn read_stream(app_handle: &AppHandle) -> Result<(), String> {
+    let client = reqwest::Client::new();
+    let mut response = client
+        .get("http://localhost:8888/queue/events")
+        .send()
+        .await
+        .map_err(|e| format!("Failed to connect to processing engine: {}", e))?;
+
+    let mut buffer = String::new();
+    let mut data = String::new();
+
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|e| format!("Event stream interrupted: {}", e))?
+    {
+        buffer.push_str(&String::from_utf8_lossy(&chunk));
+
+        // Server-Sent Events: "data:" lines accumulate until a blank line
+        while let Some(newline) = buffer.find('\n') {
+            let line: String = buffer.drain(..=newline).collect();
+            let line = line.trim_end_matches(['\r', '\n']);
+
+            if line.is_empty() {
+                if !data.is_empty() {
+                    dispatch(app_handle, &data);
+                    data.clear();
+                }
+            } else if let Some(payload) = line.strip_prefix("data:") {
+                if !data.is_empty() {
+                    data.push('\n');
+                }
+                data.push_str(payload.trim_start());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+pub struct BackendEventStream {
+    task: Mutex<Option<JoinHandle<()>>>,
+    app_handle: AppHandle,
+}
+
+impl BackendEventStream {
+    pub fn new(app_handle: AppHandle) -> Self {
+        Self {
+            task: Mutex::new(None),
+            app_handle,
+        }
+    }
+
+    pub fn start(&self) {
+        let app_handle = self.app_handle.clone();
+        let task = tokio::spawn(async move {
+            loop {
+                if let Err(e) = read_stream(&app_handle).await {
+                    errors::record_last_error(errors::BACKEND, &e);
+                }
+                tokio::time::sleep(RECONNECT_DELAY).await;
+            }
+        });
+
+        if let Some(previous) = self.task.lock().unwrap().replace(task) {
+            previous.abort();
+        }
+    }
+
+    pub fn stop(&self) {
+        if let Some(task) = self.task.lock().unwrap().take() {
+            task.abort();
+        }
+    }
+}
+
+// Tauri commands
+#[tauri::command]
+pub async fn start_backend_events(stream: State<'_, Arc<BackendEventStream>>) -> Result<(), String> {
+    stream.start();
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn stop_backend_events(stream: State<'_, Arc<BackendEventStream>>) -> Result<(), String> {
+    stream.stop();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn job_events_carry_the_folder_id() {
+        let completed = serde_json::from_str::<BackendEvent>(
+            r#"{"type": "job_completed", "job_id": "job-1", "output_path": "out.jpg",
+                "processing_time_ms": 120, "folder_id": "studio-a"}"#,
+        )
+        .unwrap();
+        match completed {
+            BackendEvent::Completed(event) => {
+                assert_eq!(event.folder_id.as_deref(), Some("studio-a"))
+            }
+            other => panic!("unexpected event {:?}", other),
+        }
+
+        let failed = serde_json::from_str::<BackendEvent>(
+            r#"{"type": "job_failed", "job_id": "job-2", "error": "decode failed"}"#,
+        )
+        .unwrap();
+        match failed {
+            BackendEvent::Failed(event) => assert_eq!(event.folder_id, None),
+            other => panic!("unexpected event {:?}", other),
+        }
+    }
+
+    // The shapes python-backend's /queue/events writes after "data: "
+    #[test]
+    fn backend_stream_payloads_are_parsed() {
+        let status = parse_message(
+            r#"{"total_items": 3, "pending_items": 1, "processing_items": 1,
+                "completed_items": 1, "failed_items": 0, "is_paused": false}"#,
+        )
+        .unwrap();
+        match status {
+            StreamMessage::Status(status) => assert_eq!(status["pending_items"], 1),
+            other => panic!("unexpected message {:?}", other),
+        }
+
+        let started = parse_message(
+            r#"{"type": "job_started", "job_id": "7f3c", "path": "/in/a.jpg",
+                "folder_id": "studio-a"}"#,
+        )
+        .unwrap();
+        match started {
+            StreamMessage::Job(BackendEvent::Started(event)) => {
+                assert_eq!(event.path, "/in/a.jpg");
+                assert_eq!(event.folder_id.as_deref(), Some("studio-a"));
+            }
+            other => panic!("unexpected message {:?}", other),
+        }
+
+        // A job marked completed without ever being started has no timing
+        let completed = parse_message(
+            r#"{"type": "job_completed", "job_id": "7f3c", "output_path": "/out/a.jpg",
+                "processing_time_ms": null, "folder_id": null}"#,
+        )
+        .unwrap();
+        match completed {
+            StreamMessage::Job(BackendEvent::Completed(event)) => {
+                assert_eq!(event.output_path, "/out/a.jpg");
+                assert_eq!(event.processing_time_ms, None);
+                assert_eq!(event.folder_id, None);
+            }
+            other => panic!("unexpected message {:?}", other),
+        }
+
+        assert!(parse_message(r#"{"type": "job_progress", "job_id": "7f3c"}"#).is_err());
+        assert!(parse_message("not json").is_err());
+    }
+}
@@ -0,0 +1,435 @@
+use crate::commands::ensure_backend_storage;
+use crate::pipeline::{execute_request, ProcessingProfile, ProcessingRequest, ProcessingResult};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet, VecDeque};
+use std::path::Path;
+use std::sync::Mutex;
+
+// Batches kept for retry_failed_batch, oldest dropped first
+const MAX_BATCH_HISTORY: usize = 50;
+
+struct BatchEntry {
+    request: ProcessingRequest,
+    success: bool,
+}
+
+static BATCH_HISTORY: Mutex<VecDeque<(String, Vec<BatchEntry>)>> = Mutex::new(VecDeque::new());
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutputConflict {
+    pub output_path: String,
+    pub inputs: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutputMapping {
+    pub input_path: String,
+    pub output_path: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutputConflictReport {
+    pub conflicts: Vec<OutputConflict>,
+    // Full input -> output mapping, disambiguated when auto_resolve is set
+    pub mappings: Vec<OutputMapping>,
+}
+
+fn output_for(input: &str, output_dir: &Path) -> String {
+    let file_name = Path::new(input)
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| input.to_string());
+    output_dir.join(file_name).to_string_lossy().to_string()
+}
+
+// Case-insensitive filesystems treat IMG.jpg and img.JPG as the same file
+fn collision_key(path: &str) -> String {
+    path.to_lowercase()
+}
+
+// "a/day1/IMG_1.jpg" -> "IMG_1_day1.jpg", falling back to a counter if the
+// subfolder name still collides
+fn disambiguate(input: &str, output_dir: &Path, taken: &HashSet<String>) -> String {
+    let input = Path::new(input);
+    let stem = input
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let extension = input
+        .extension()
+        .map(|ext| format!(".{}", ext.to_string_lossy()))
+        .unwrap_or_default();
+    let folder = input
+        .parent()
+        .and_then(|parent| parent.file_name())
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| "root".to_string());
+
+    let mut candidate = output_dir
+        .join(format!("{}_{}{}", stem, folder, extension))
+        .to_string_lossy()
+        .to_string();
+    let mut counter = 2;
+    while taken.contains(&collision_key(&candidate)) {
+        candidate = output_dir
+            .join(format!("{}_{}_{}{}", stem, folder, counter, extension))
+            .to_string_lossy()
+            .to_string();
+        counter += 1;
+    }
+    candidate
+}
+
+// Inputs grouped by the output file they would write, keyed by collision_key
+fn group_by_output(
+    pairs: impl IntoIterator<Item = (String, String)>,
+) -> BTreeMap<String, (String, Vec<String>)> {
+    let mut groups: BTreeMap<String, (String, Vec<String>)> = BTreeMap::new();
+    for (input, output) in pairs {
+        groups
+            .entry(collision_key(&output))
+            .or_insert_with(|| (output, Vec::new()))
+            .1
+            .push(input);
+    }
+    groups
+}
+
+fn conflicts_in(groups: &BTreeMap<String, (String, Vec<String>)>) -> Vec<OutputConflict> {
+    groups
+        .values()
+        .filter(|(_, inputs)| inputs.len() > 1)
+        .map(|(output, inputs)| OutputConflict {
+            output_path: output.clone(),
+            inputs: inputs.clone(),
+        })
+        .collect()
+}
+
+fn find_conflicts(
+    input_paths: &[String],
+    output_dir: &Path,
+    auto_resolve: bool,
+) -> OutputConflictReport {
+    let groups = group_by_output(
+        input_paths
+            .iter()
+            .map(|input| (input.clone(), output_for(input, output_dir))),
+    );
+    let conflicts = conflicts_in(&groups);
+
+    let mut taken: HashSet<String> = groups.keys().cloned().collect();
+    let mut mappings = Vec::with_capacity(input_paths.len());
+    for input in input_paths {
+        let output = output_for(input, output_dir);
+        let colliding = groups[&collision_key(&output)].1.len() > 1;
+        let output = if auto_resolve && colliding {
+            let resolved = disambiguate(input, output_dir, &taken);
+            taken.insert(collision_key(&resolved));
+            resolved
+        } else {
+            output
+        };
+        mappings.push(OutputMapping {
+            input_path: input.clone(),
+            output_path: output,
+        });
+    }
+
+    OutputConflictReport {
+        conflicts,
+        mappings,
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchSummary {
+    pub batch_id: String,
+    pub results: Vec<ProcessingResult>,
+    pub succeeded: usize,
+    pub failed: usize,
+}
+
+// Two requests writing the same file would silently overwrite each other
+fn check_batch_outputs(requests: &[ProcessingRequest]) -> Result<(), String> {
+    let groups = group_by_output(
+        requests
+            .iter()
+            .map(|request| (request.input_path.clone(), request.output_path.clone())),
+    );
+    let conflicts = conflicts_in(&groups);
+    if conflicts.is_empty() {
+        return Ok(());
+    }
+    let outputs: Vec<&str> = conflicts
+        .iter()
+        .map(|conflict| conflict.output_path.as_str())
+        .collect();
+    Err(format!(
+        "Batch writes more than one image to the same output: {}",
+        outputs.join(", ")
+    ))
+}
+
+fn failed_result(request: &ProcessingRequest, error: String) -> ProcessingResult {
+    ProcessingResult {
+        session_id: None,
+        success: false,
+        input_path: request.input_path.clone(),
+        input_paths: Vec::new(),
+        output_path: request.output_path.clone(),
+        operations_applied: 0,
+        processing_time_ms: 0,
+        profile: ProcessingProfile::default(),
+        folder_id: request.folder_id.clone(),
+        encoding: None,
+        models: Vec::new(),
+        output_sharpening: None,
+        warning: None,
+        error: Some(error),
+    }
+}
+
+// Requests that never reached the backend still get a result, so every
+// batch entry has an outcome
+async fn run_request(request: &ProcessingRequest) -> ProcessingResult {
+    match execute_request(request).await {
+        Ok(result) => result,
+        Err(error) => failed_result(request, error),
+    }
+}
+
+fn failed_entries(entries: &[BatchEntry]) -> Vec<(usize, ProcessingRequest)> {
+    entries
+        .iter()
+        .enumerate()
+        .filter(|(_, entry)| !entry.success)
+        .map(|(index, entry)| (index, entry.request.clone()))
+        .collect()
+}
+
+fn summarize(batch_id: String, results: Vec<ProcessingResult>) -> BatchSummary {
+    let succeeded = results.iter().filter(|result| result.success).count();
+    BatchSummary {
+        batch_id,
+        failed: results.len() - succeeded,
+        succeeded,
+        results,
+    }
+}
+
+// Tauri commands
+#[tauri::command]
+pub async fn detect_output_conflicts(
+    input_paths: Vec<String>,
+    output_dir: String,
+    auto_resolve: bool,
+) -> Result<OutputConflictReport, String> {
+    if output_dir.trim().is_empty() {
+        return Err("Output folder is required".to_string());
+    }
+    Ok(find_conflicts(&input_paths, Path::new(&output_dir), auto_resolve))
+}
+
+#[tauri::command]
+pub async fn execute_batch(requests: Vec<ProcessingRequest>) -> Result<BatchSummary, String> {
+    if requests.is_empty() {
+        return Err("Batch is empty".to_string());
+    }
+    check_batch_outputs(&requests)?;
+
+    // Outputs are assumed to be roughly the size of their inputs
+    let estimated_bytes = requests
+        .iter()
+        .filter_map(|request| std::fs::metadata(&request.input_path).ok())
+        .map(|metadata| metadata.len())
+        .sum();
+    ensure_backend_storage(estimated_bytes).await?;
+
+    let batch_id = format!("batch-{}", chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default());
+    let mut entries = Vec::with_capacity(requests.len());
+    let mut results = Vec::with_capacity(requests.len());
+    for request in requests {
+        let result = run_request(&request).await;
+        entries.push(BatchEntry {
+            request,
+            success: result.success,
+        });
+        results.push(result);
+    }
+
+    let mut history = BATCH_HISTORY.lock().unwrap();
+    if history.len() >= MAX_BATCH_HISTORY {
+        history.pop_front();
+    }
+    history.push_back((batch_id.clone(), entries));
+    drop(history);
+
+    Ok(summarize(batch_id, results))
+}
+
+// Re-runs only the entries that failed last time; entries that now succeed
+// are skipped by any later retry
+#[tauri::command]
+pub async fn retry_failed_batch(batch_id: String) -> Result<Vec<ProcessingResult>, String> {
+    let failed = failed_entries(
+        &BATCH_HISTORY
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|(id, _)| *id == batch_id)
+            .ok_or_else(|| format!("No batch recorded with id '{}'", batch_id))?
+            .1,
+    );
+
+    let mut results = Vec::with_capacity(failed.len());
+    for (index, request) in failed {
+        let result = run_request(&request).await;
+        if let Some((_, entries)) = BATCH_HISTORY
+            .lock()
+            .unwrap()
+            .iter_mut()
+            .find(|(id, _)| *id == batch_id)
+        {
+            entries[index].success = result.success;
+        }
+        results.push(result);
+    }
+
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn paths(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    fn outputs(report: &OutputConflictReport) -> Vec<&str> {
+        report
+            .mappings
+            .iter()
+            .map(|mapping| mapping.output_path.as_str())
+            .collect()
+    }
+
+    fn out(file_name: &str) -> String {
+        Path::new("/out").join(file_name).to_string_lossy().to_string()
+    }
+
+    fn request(input: &str, output: &str) -> ProcessingRequest {
+        ProcessingRequest::new(input.to_string(), output.to_string(), Vec::new())
+    }
+
+    fn succeeded(request: &ProcessingRequest) -> ProcessingResult {
+        ProcessingResult {
+            success: true,
+            error: None,
+            ..failed_result(request, String::new())
+        }
+    }
+
+    #[test]
+    fn mixed_batch_reports_and_retries_only_failures() {
+        let requests = [
+            request("/in/a.jpg", &out("a.jpg")),
+            request("/in/b.jpg", &out("b.jpg")),
+            request("/in/c.jpg", &out("c.jpg")),
+        ];
+        let results = vec![
+            succeeded(&requests[0]),
+            failed_result(&requests[1], "Processing engine error: corrupt".to_string()),
+            failed_result(&requests[2], "Failed to connect to processing engine".to_string()),
+        ];
+        let mut entries: Vec<BatchEntry> = requests
+            .iter()
+            .zip(&results)
+            .map(|(request, result)| BatchEntry {
+                request: request.clone(),
+                success: result.success,
+            })
+            .collect();
+
+        let summary = summarize("batch-1".to_string(), results);
+        assert_eq!((summary.succeeded, summary.failed), (1, 2));
+        assert_eq!(summary.results[1].input_path, "/in/b.jpg");
+
+        let retry: Vec<usize> = failed_entries(&entries).iter().map(|(i, _)| *i).collect();
+        assert_eq!(retry, [1, 2]);
+
+        // c.jpg succeeds on retry, so only b.jpg is left for the next one
+        entries[2].success = true;
+        let retry = failed_entries(&entries);
+        assert_eq!(retry.len(), 1);
+        assert_eq!(retry[0].1.input_path, "/in/b.jpg");
+    }
+
+    #[test]
+    fn batch_with_colliding_outputs_is_rejected() {
+        let distinct = [
+            request("/a/IMG_1.jpg", &out("one.jpg")),
+            request("/b/IMG_1.jpg", &out("two.jpg")),
+        ];
+        assert!(check_batch_outputs(&distinct).is_ok());
+
+        let colliding = [
+            request("/a/IMG_1.jpg", &out("IMG_1.jpg")),
+            request("/b/IMG_1.jpg", &out("img_1.JPG")),
+        ];
+        assert_eq!(
+            check_batch_outputs(&colliding).unwrap_err(),
+            format!("Batch writes more than one image to the same output: {}", out("IMG_1.jpg"))
+        );
+    }
+
+    #[test]
+    fn known_collision_set_is_reported_and_resolved() {
+        let inputs = paths(&[
+            "/shoot/day1/IMG_1.jpg",
+            "/shoot/day2/IMG_1.jpg",
+            "/shoot/day2/edits/img_1.JPG",
+            "/shoot/day1/IMG_2.jpg",
+        ]);
+
+        let report = find_conflicts(&inputs, Path::new("/out"), false);
+        assert_eq!(report.conflicts.len(), 1);
+        assert_eq!(report.conflicts[0].output_path, out("IMG_1.jpg"));
+        assert_eq!(report.conflicts[0].inputs, inputs[..3]);
+        assert_eq!(
+            outputs(&report),
+            [out("IMG_1.jpg"), out("IMG_1.jpg"), out("img_1.JPG"), out("IMG_2.jpg")]
+        );
+
+        let resolved = find_conflicts(&inputs, Path::new("/out"), true);
+        assert_eq!(resolved.conflicts.len(), 1);
+        assert_eq!(
+            outputs(&resolved),
+            [
+                out("IMG_1_day1.jpg"),
+                out("IMG_1_day2.jpg"),
+                out("img_1_edits.JPG"),
+                out("IMG_2.jpg")
+            ]
+        );
+    }
+
+    #[test]
+    fn same_subfolder_name_falls_back_to_a_counter() {
+        let inputs = paths(&["/a/raw/IMG_1.jpg", "/b/raw/IMG_1.jpg", "/c/IMG_1.jpg"]);
+        let report = find_conflicts(&inputs, Path::new("/out"), true);
+        assert_eq!(
+            outputs(&report),
+            [out("IMG_1_raw.jpg"), out("IMG_1_raw_2.jpg"), out("IMG_1_c.jpg")]
+        );
+    }
+
+    #[test]
+    fn distinct_names_have_no_conflicts() {
+        let inputs = paths(&["/a/IMG_1.jpg", "/a/IMG_2.jpg"]);
+        let report = find_conflicts(&inputs, Path::new("/out"), true);
+        assert!(report.conflicts.is_empty());
+        assert_eq!(outputs(&report), [out("IMG_1.jpg"), out("IMG_2.jpg")]);
+    }
+}
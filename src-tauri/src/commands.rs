@@ -1,15 +1,28 @@
+use crate::hot_folder::DEFAULT_BACKEND_URL;
 use serde::{Deserialize, Serialize};
 use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
 
 #[derive(Serialize, Deserialize)]
 pub struct ImageInfo {
     path: String,
-    width: u32,
-    height: u32,
+    pub(crate) width: u32,
+    pub(crate) height: u32,
     format: String,
     file_size: u64,
 }
 
+// An encoded preview image returned inline rather than as a file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PreviewResponse {
+    pub width: u32,
+    pub height: u32,
+    pub format: String,
+    pub data: String, // base64
+    pub generation_time: u64, // milliseconds
+}
+
 #[derive(Serialize)]
 struct ImageInfoRequest {
     path: String,
@@ -48,10 +61,10 @@ pub async fn get_image_info(path: String) -> Result<ImageInfo, String> {
 }
 
 #[derive(Deserialize)]
-struct HealthResponse {
-    status: String,
-    version: String,
-    gpu_available: bool,
+pub(crate) struct HealthResponse {
+    pub(crate) status: String,
+    pub(crate) version: String,
+    pub(crate) gpu_available: bool,
 }
 
 #[tauri::command]
@@ -73,9 +86,571 @@ pub async fn check_backend_health() -> Result<bool, String> {
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SupportedFormats {
+    pub input: Vec<String>,
+    pub output: Vec<String>,
+}
+
+#[tauri::command]
+pub async fn get_supported_formats() -> Result<SupportedFormats, String> {
+    fetch_supported_formats(DEFAULT_BACKEND_URL).await
+}
+
+// Hot folders with their own backend_url check against that backend
+pub async fn fetch_supported_formats(backend_url: &str) -> Result<SupportedFormats, String> {
+    let client = reqwest::Client::new();
+
+    let response = client
+        .get(format!("{}/formats", backend_url))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to connect to processing engine: {}", e))?;
+
+    if !response.status().is_success() {
+        let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+        return Err(format!("Processing engine error: {}", error_text));
+    }
+
+    response
+        .json::<SupportedFormats>()
+        .await
+        .map_err(|e| format!("Invalid response from processing engine: {}", e))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ColorSwatch {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub coverage: f32, // percent of the image
+}
+
+#[derive(Serialize)]
+struct PaletteRequest {
+    path: String,
+    count: u32,
+}
+
+const MAX_PALETTE_COLORS: u32 = 16;
+
+// Grayscale and flat images can yield repeated clusters; merge them so the
+// palette holds distinct colors only
+fn merge_swatches(swatches: Vec<ColorSwatch>) -> Vec<ColorSwatch> {
+    let mut palette: Vec<ColorSwatch> = Vec::with_capacity(swatches.len());
+    for swatch in swatches {
+        match palette
+            .iter_mut()
+            .find(|existing| (existing.r, existing.g, existing.b) == (swatch.r, swatch.g, swatch.b))
+        {
+            Some(existing) => existing.coverage += swatch.coverage,
+            None => palette.push(swatch),
+        }
+    }
+    palette.sort_by(|a, b| b.coverage.total_cmp(&a.coverage));
+    palette
+}
+
+#[tauri::command]
+pub async fn extract_palette(path: String, count: u32) -> Result<Vec<ColorSwatch>, String> {
+    if !(1..=MAX_PALETTE_COLORS).contains(&count) {
+        return Err(format!("Color count must be between 1 and {}", MAX_PALETTE_COLORS));
+    }
+
+    // Validate path exists
+    if !Path::new(&path).exists() {
+        return Err("File not found".to_string());
+    }
+
+    // Call Python backend
+    let client = reqwest::Client::new();
+    let response = client
+        .post("http://localhost:8888/image/palette")
+        .json(&PaletteRequest { path, count })
+        .send()
+        .await
+        .map_err(|e| format!("Failed to connect to processing engine: {}", e))?;
+
+    if !response.status().is_success() {
+        let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+        return Err(format!("Processing engine error: {}", error_text));
+    }
+
+    let swatches = response
+        .json::<Vec<ColorSwatch>>()
+        .await
+        .map_err(|e| format!("Invalid response from processing engine: {}", e))?;
+
+    Ok(merge_swatches(swatches))
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ColorChart {
+    ColorChecker24,
+}
+
+// CIEDE2000; under 2 is hard to see, under 3 is a common studio tolerance
+const DEFAULT_MAX_DELTA_E: f32 = 3.0;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PatchAccuracy {
+    pub name: String,
+    pub delta_e: f32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ColorAccuracyReport {
+    pub chart: ColorChart,
+    pub patches: Vec<PatchAccuracy>,
+    pub average_delta_e: f32,
+    pub max_delta_e: f32,
+    pub threshold: f32,
+    pub passed: bool, // average within threshold
+}
+
+#[derive(Serialize)]
+struct ColorAccuracyRequest {
+    path: String,
+    chart: ColorChart,
+}
+
+#[derive(Deserialize)]
+struct ColorAccuracyResponse {
+    detected: bool,
+    #[serde(default)]
+    patches: Vec<PatchAccuracy>,
+}
+
+fn accuracy_report(
+    chart: ColorChart,
+    measured: ColorAccuracyResponse,
+    threshold: f32,
+) -> Result<ColorAccuracyReport, String> {
+    if !measured.detected || measured.patches.is_empty() {
+        return Err("No color chart detected in the image".to_string());
+    }
+
+    let total: f32 = measured.patches.iter().map(|patch| patch.delta_e).sum();
+    let average_delta_e = total / measured.patches.len() as f32;
+    let max_delta_e = measured
+        .patches
+        .iter()
+        .map(|patch| patch.delta_e)
+        .fold(0.0, f32::max);
+
+    Ok(ColorAccuracyReport {
+        chart,
+        patches: measured.patches,
+        average_delta_e,
+        max_delta_e,
+        threshold,
+        passed: average_delta_e <= threshold,
+    })
+}
+
+#[tauri::command]
+pub async fn measure_color_accuracy(
+    image_path: String,
+    chart_type: ColorChart,
+    max_delta_e: Option<f32>,
+) -> Result<ColorAccuracyReport, String> {
+    let threshold = max_delta_e.unwrap_or(DEFAULT_MAX_DELTA_E);
+    if threshold.is_nan() || threshold <= 0.0 {
+        return Err("Delta E threshold must be greater than zero".to_string());
+    }
+
+    // Validate path exists
+    if !Path::new(&image_path).exists() {
+        return Err("File not found".to_string());
+    }
+
+    // Call Python backend
+    let client = reqwest::Client::new();
+    let response = client
+        .post("http://localhost:8888/image/color-accuracy")
+        .json(&ColorAccuracyRequest {
+            path: image_path,
+            chart: chart_type,
+        })
+        .send()
+        .await
+        .map_err(|e| format!("Failed to connect to processing engine: {}", e))?;
+
+    if !response.status().is_success() {
+        let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+        return Err(format!("Processing engine error: {}", error_text));
+    }
+
+    let measured = response
+        .json::<ColorAccuracyResponse>()
+        .await
+        .map_err(|e| format!("Invalid response from processing engine: {}", e))?;
+
+    accuracy_report(chart_type, measured, threshold)
+}
+
+// Confidence reported for the centered fallback when no subject is found
+const FALLBACK_CROP_CONFIDENCE: f32 = 0.1;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct Region {
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct DetectedSubject {
+    #[serde(flatten)]
+    region: Region,
+    confidence: f32,
+}
+
+#[derive(Deserialize)]
+struct SubjectsResponse {
+    #[serde(default)]
+    subjects: Vec<DetectedSubject>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CropSuggestion {
+    pub aspect_ratio: (u32, u32),
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+    pub confidence: f32,
+    pub subject_detected: bool,
+}
+
+fn union(subjects: &[DetectedSubject]) -> Option<Region> {
+    subjects.iter().map(|subject| subject.region).reduce(|a, b| {
+        let x = a.x.min(b.x);
+        let y = a.y.min(b.y);
+        Region {
+            x,
+            y,
+            width: a.x.saturating_add(a.width).max(b.x.saturating_add(b.width)) - x,
+            height: a.y.saturating_add(a.height).max(b.y.saturating_add(b.height)) - y,
+        }
+    })
+}
+
+// Largest crop of the requested ratio that fits the image, centered on the
+// subject (or the image) and shifted back inside the frame
+fn crop_around(
+    width: u32,
+    height: u32,
+    (a, b): (u32, u32),
+    subject: Option<Region>,
+) -> Result<Region, String> {
+    let (w, h) = (width as u64, height as u64);
+    let (crop_w, crop_h) = if w * b as u64 <= h * a as u64 {
+        (w, w * b as u64 / a as u64)
+    } else {
+        (h * a as u64 / b as u64, h)
+    };
+    // Extreme ratios round down to nothing on small images
+    if crop_w == 0 || crop_h == 0 {
+        return Err(format!(
+            "Aspect ratio {}:{} leaves no crop on a {}x{} image",
+            a, b, width, height
+        ));
+    }
+
+    let (center_x, center_y) = match subject {
+        Some(region) => (
+            region.x as u64 + region.width as u64 / 2,
+            region.y as u64 + region.height as u64 / 2,
+        ),
+        None => (w / 2, h / 2),
+    };
+    let x = center_x.saturating_sub(crop_w / 2).min(w - crop_w);
+    let y = center_y.saturating_sub(crop_h / 2).min(h - crop_h);
+
+    Ok(Region {
+        x: x as u32,
+        y: y as u32,
+        width: crop_w as u32,
+        height: crop_h as u32,
+    })
+}
+
+// Share of the subject's area that survives the crop
+fn coverage(subject: Region, crop: Region) -> f32 {
+    let overlap_w = subject
+        .x
+        .saturating_add(subject.width)
+        .min(crop.x + crop.width)
+        .saturating_sub(subject.x.max(crop.x));
+    let overlap_h = subject
+        .y
+        .saturating_add(subject.height)
+        .min(crop.y + crop.height)
+        .saturating_sub(subject.y.max(crop.y));
+    let area = subject.width as f32 * subject.height as f32;
+    if area == 0.0 {
+        return 0.0;
+    }
+    overlap_w as f32 * overlap_h as f32 / area
+}
+
+#[derive(Serialize)]
+struct SubjectsRequest {
+    path: String,
+}
+
+#[tauri::command]
+pub async fn suggest_crops(
+    image_path: String,
+    aspect_ratios: Vec<(u32, u32)>,
+) -> Result<Vec<CropSuggestion>, String> {
+    if aspect_ratios.is_empty() {
+        return Err("At least one aspect ratio is required".to_string());
+    }
+    if aspect_ratios.iter().any(|&(a, b)| a == 0 || b == 0) {
+        return Err("Aspect ratio terms must be greater than zero".to_string());
+    }
+
+    let info = get_image_info(image_path.clone()).await?;
+
+    // Call Python backend
+    let client = reqwest::Client::new();
+    let response = client
+        .post("http://localhost:8888/image/subjects")
+        .json(&SubjectsRequest { path: image_path })
+        .send()
+        .await
+        .map_err(|e| format!("Failed to connect to processing engine: {}", e))?;
+
+    if !response.status().is_success() {
+        let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+        return Err(format!("Processing engine error: {}", error_text));
+    }
+
+    let detected = response
+        .json::<SubjectsResponse>()
+        .await
+        .map_err(|e| format!("Invalid response from processing engine: {}", e))?;
+
+    let subject = union(&detected.subjects);
+    let subject_confidence = detected
+        .subjects
+        .iter()
+        .map(|subject| subject.confidence)
+        .fold(0.0, f32::max);
+
+    aspect_ratios
+        .into_iter()
+        .map(|aspect_ratio| {
+            let crop = crop_around(info.width, info.height, aspect_ratio, subject)?;
+            let confidence = match subject {
+                Some(subject) => subject_confidence * coverage(subject, crop),
+                None => FALLBACK_CROP_CONFIDENCE,
+            };
+            Ok(CropSuggestion {
+                aspect_ratio,
+                x: crop.x,
+                y: crop.y,
+                width: crop.width,
+                height: crop.height,
+                confidence,
+                subject_detected: subject.is_some(),
+            })
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiskSpace {
+    pub free_bytes: u64,
+    pub total_bytes: u64,
+}
+
+// Batches check space per submission run; avoid hammering the backend
+const DISK_SPACE_CACHE_TTL: Duration = Duration::from_secs(10);
+static DISK_SPACE_CACHE: Mutex<Option<(Instant, DiskSpace)>> = Mutex::new(None);
+
+#[tauri::command]
+pub async fn get_backend_disk_space() -> Result<DiskSpace, String> {
+    if let Some((fetched_at, space)) = DISK_SPACE_CACHE.lock().unwrap().as_ref() {
+        if fetched_at.elapsed() < DISK_SPACE_CACHE_TTL {
+            return Ok(space.clone());
+        }
+    }
+
+    let client = reqwest::Client::new();
+    let response = client
+        .get("http://localhost:8888/system/disk-space")
+        .send()
+        .await
+        .map_err(|e| format!("Failed to connect to processing engine: {}", e))?;
+
+    if !response.status().is_success() {
+        let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+        return Err(format!("Processing engine error: {}", error_text));
+    }
+
+    let space = response
+        .json::<DiskSpace>()
+        .await
+        .map_err(|e| format!("Invalid response from processing engine: {}", e))?;
+
+    *DISK_SPACE_CACHE.lock().unwrap() = Some((Instant::now(), space.clone()));
+    Ok(space)
+}
+
+fn check_backend_storage(space: &DiskSpace, estimated_bytes: u64) -> Result<(), String> {
+    if space.free_bytes < estimated_bytes {
+        return Err(format!(
+            "Insufficient backend storage: batch needs about {} bytes, {} bytes free",
+            estimated_bytes, space.free_bytes
+        ));
+    }
+    Ok(())
+}
+
+// Fails when the backend reports less free space than a batch is expected to
+// write, or when it can't report its space at all
+pub async fn ensure_backend_storage(estimated_bytes: u64) -> Result<(), String> {
+    let space = get_backend_disk_space()
+        .await
+        .map_err(|e| format!("Could not verify backend storage: {}", e))?;
+    check_backend_storage(&space, estimated_bytes)
+}
+
 #[tauri::command]
 pub async fn process_image(path: String) -> Result<String, String> {
     // TODO: Call Python backend for processing
     // For now, just return the same path
     Ok(path)
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn batch_must_fit_in_backend_free_space() {
+        let space = DiskSpace {
+            free_bytes: 1_000,
+            total_bytes: 10_000,
+        };
+        assert!(check_backend_storage(&space, 999).is_ok());
+        assert!(check_backend_storage(&space, 1_000).is_ok());
+        assert_eq!(
+            check_backend_storage(&space, 1_001).unwrap_err(),
+            "Insufficient backend storage: batch needs about 1001 bytes, 1000 bytes free"
+        );
+    }
+
+    fn subject(x: u32, y: u32, width: u32, height: u32) -> DetectedSubject {
+        DetectedSubject {
+            region: Region {
+                x,
+                y,
+                width,
+                height,
+            },
+            confidence: 0.9,
+        }
+    }
+
+    #[test]
+    fn subject_union_saturates_at_the_coordinate_limit() {
+        let region = union(&[subject(10, 20, 30, 40), subject(u32::MAX - 5, 0, 100, 10)]).unwrap();
+        assert_eq!((region.x, region.y), (10, 0));
+        assert_eq!((region.width, region.height), (u32::MAX - 10, 60));
+        assert!(union(&[]).is_none());
+    }
+
+    #[test]
+    fn backend_subjects_are_combined_into_one_region() {
+        let detected: SubjectsResponse = serde_json::from_str(
+            r#"{"subjects": [
+                {"x": 380, "y": 0, "width": 20, "height": 40, "confidence": 0.88},
+                {"x": 10, "y": 20, "width": 30, "height": 40, "confidence": 0.5}
+            ]}"#,
+        )
+        .unwrap();
+        let region = union(&detected.subjects).unwrap();
+        assert_eq!((region.x, region.y, region.width, region.height), (10, 0, 390, 60));
+    }
+
+    #[test]
+    fn crop_is_centered_on_the_subject_inside_the_frame() {
+        let square = crop_around(400, 200, (1, 1), None).unwrap();
+        assert_eq!((square.x, square.y, square.width, square.height), (100, 0, 200, 200));
+
+        let near_edge = union(&[subject(360, 50, 20, 20)]);
+        let crop = crop_around(400, 200, (1, 1), near_edge).unwrap();
+        assert_eq!((crop.x, crop.width), (200, 200));
+    }
+
+    #[test]
+    fn extreme_ratio_that_rounds_to_nothing_is_rejected() {
+        assert_eq!(
+            crop_around(400, 200, (1, 1000), None).unwrap_err(),
+            "Aspect ratio 1:1000 leaves no crop on a 400x200 image"
+        );
+        assert!(crop_around(4000, 2000, (1, 1000), None).is_ok());
+    }
+
+    #[test]
+    fn repeated_palette_colors_are_merged_by_coverage() {
+        // Shape of the backend's /image/palette response for a flat gray image
+        let swatches: Vec<ColorSwatch> = serde_json::from_str(
+            r#"[
+                {"r": 128, "g": 128, "b": 128, "coverage": 40.0},
+                {"r": 255, "g": 0, "b": 0, "coverage": 35.0},
+                {"r": 128, "g": 128, "b": 128, "coverage": 25.0}
+            ]"#,
+        )
+        .unwrap();
+
+        let palette = merge_swatches(swatches);
+        let colors: Vec<(u8, u8, u8, f32)> = palette
+            .iter()
+            .map(|swatch| (swatch.r, swatch.g, swatch.b, swatch.coverage))
+            .collect();
+        assert_eq!(colors, [(128, 128, 128, 65.0), (255, 0, 0, 35.0)]);
+    }
+
+    #[test]
+    fn color_accuracy_passes_on_the_average_delta_e() {
+        let measured: ColorAccuracyResponse = serde_json::from_str(
+            r#"{"detected": true, "patches": [
+                {"name": "dark_skin", "delta_e": 1.0},
+                {"name": "blue", "delta_e": 4.5},
+                {"name": "white", "delta_e": 1.5}
+            ]}"#,
+        )
+        .unwrap();
+        let report = accuracy_report(ColorChart::ColorChecker24, measured, 3.0).unwrap();
+        assert_eq!(report.average_delta_e, 7.0 / 3.0);
+        assert_eq!(report.max_delta_e, 4.5);
+        assert!(report.passed);
+        assert_eq!(
+            serde_json::to_value(ColorChart::ColorChecker24).unwrap(),
+            "color_checker24"
+        );
+
+        let strict: ColorAccuracyResponse = serde_json::from_str(
+            r#"{"detected": true, "patches": [{"name": "blue", "delta_e": 2.5}]}"#,
+        )
+        .unwrap();
+        assert!(!accuracy_report(ColorChart::ColorChecker24, strict, 2.0).unwrap().passed);
+    }
+
+    #[test]
+    fn undetected_chart_is_an_error() {
+        let measured: ColorAccuracyResponse =
+            serde_json::from_str(r#"{"detected": false}"#).unwrap();
+        assert_eq!(
+            accuracy_report(ColorChart::ColorChecker24, measured, 3.0).err().unwrap(),
+            "No color chart detected in the image"
+        );
+    }
+}
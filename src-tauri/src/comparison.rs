@@ -0,0 +1,165 @@
+use crate::commands::{get_image_info, PreviewResponse};
+use serde::{Deserialize, Serialize};
+use std::time::Instant;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CompareLayout {
+    SideBySide,
+    // Original left of the split, processed right; position is 0.0 - 1.0 of the width
+    SplitSlider { position: f32 },
+    Stacked,
+}
+
+impl CompareLayout {
+    // Both images are composited at the original's size
+    fn output_size(&self, width: u32, height: u32) -> (u32, u32) {
+        match self {
+            CompareLayout::SideBySide => (width * 2, height),
+            CompareLayout::SplitSlider { .. } => (width, height),
+            CompareLayout::Stacked => (width, height * 2),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct CompareRequest<'a> {
+    original_path: &'a str,
+    processed_path: &'a str,
+    layout: CompareLayout,
+    width: u32,
+    height: u32,
+}
+
+// The composite comes back inline, so nothing is left on disk
+#[derive(Deserialize)]
+struct CompareResponse {
+    width: u32,
+    height: u32,
+    format: String,
+    data: String,
+}
+
+fn preview_from(
+    composite: CompareResponse,
+    (width, height): (u32, u32),
+    generation_time: u64,
+) -> Result<PreviewResponse, String> {
+    if (composite.width, composite.height) != (width, height) {
+        return Err(format!(
+            "Comparison preview is {}x{}, expected {}x{}",
+            composite.width, composite.height, width, height
+        ));
+    }
+    Ok(PreviewResponse {
+        width,
+        height,
+        format: composite.format,
+        data: composite.data,
+        generation_time,
+    })
+}
+
+// Tauri commands
+#[tauri::command]
+pub async fn generate_comparison_preview(
+    original_path: String,
+    processed_path: String,
+    layout: CompareLayout,
+    resize_to_match: Option<bool>,
+) -> Result<PreviewResponse, String> {
+    if let CompareLayout::SplitSlider { position } = layout {
+        if !(0.0..=1.0).contains(&position) {
+            return Err("Split position must be between 0 and 1".to_string());
+        }
+    }
+
+    let started = Instant::now();
+    let original = get_image_info(original_path.clone()).await?;
+    let processed = get_image_info(processed_path.clone()).await?;
+    let same_size = (original.width, original.height) == (processed.width, processed.height);
+    if !same_size && !resize_to_match.unwrap_or(true) {
+        return Err(format!(
+            "Images differ in size ({}x{} vs {}x{})",
+            original.width, original.height, processed.width, processed.height
+        ));
+    }
+
+    let (width, height) = layout.output_size(original.width, original.height);
+
+    // Call Python backend
+    let client = reqwest::Client::new();
+    let response = client
+        .post("http://localhost:8888/image/compare")
+        .json(&CompareRequest {
+            original_path: &original_path,
+            processed_path: &processed_path,
+            layout,
+            width,
+            height,
+        })
+        .send()
+        .await
+        .map_err(|e| format!("Failed to connect to processing engine: {}", e))?;
+
+    if !response.status().is_success() {
+        let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+        return Err(format!("Processing engine error: {}", error_text));
+    }
+
+    let composite = response
+        .json::<CompareResponse>()
+        .await
+        .map_err(|e| format!("Invalid response from processing engine: {}", e))?;
+
+    preview_from(composite, (width, height), started.elapsed().as_millis() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn output_size_keeps_each_image_at_its_aspect_ratio() {
+        let split = CompareLayout::SplitSlider { position: 0.5 };
+        for (width, height) in [(1200, 800), (800, 1200), (640, 640)] {
+            assert_eq!(CompareLayout::SideBySide.output_size(width, height), (width * 2, height));
+            assert_eq!(split.output_size(width, height), (width, height));
+            assert_eq!(CompareLayout::Stacked.output_size(width, height), (width, height * 2));
+        }
+    }
+
+    fn composite(width: u32, height: u32) -> CompareResponse {
+        CompareResponse {
+            width,
+            height,
+            format: "jpeg".to_string(),
+            data: "/9j/4AAQ".to_string(),
+        }
+    }
+
+    #[test]
+    fn composite_is_returned_inline_at_the_expected_size() {
+        let (width, height) = CompareLayout::SideBySide.output_size(1200, 800);
+        let preview = preview_from(composite(2400, 800), (width, height), 42).unwrap();
+        assert_eq!((preview.width, preview.height), (2400, 800));
+        assert_eq!(preview.data, "/9j/4AAQ");
+        assert_eq!(preview.generation_time, 42);
+
+        assert_eq!(
+            preview_from(composite(1200, 800), (width, height), 42).err().unwrap(),
+            "Comparison preview is 1200x800, expected 2400x800"
+        );
+    }
+
+    #[test]
+    fn layouts_use_tagged_json() {
+        let layout: CompareLayout =
+            serde_json::from_str(r#"{"type":"split_slider","position":0.25}"#).unwrap();
+        assert!(matches!(layout, CompareLayout::SplitSlider { position } if position == 0.25));
+        assert_eq!(
+            serde_json::to_string(&CompareLayout::SideBySide).unwrap(),
+            r#"{"type":"side_by_side"}"#
+        );
+    }
+}
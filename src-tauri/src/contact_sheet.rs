@@ -0,0 +1,192 @@
+use crate::pipeline::{ProcessingProfile, ProcessingResult};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::time::Instant;
+
+const MAX_COLUMNS: u32 = 20;
+const MIN_THUMB_SIZE: u32 = 32;
+const MAX_THUMB_SIZE: u32 = 1024;
+
+#[derive(Serialize)]
+struct ContactSheetRequest<'a> {
+    paths: &'a [String],
+    columns: u32,
+    thumb_size: u32,
+    labels: bool,
+    output_path: &'a str,
+}
+
+#[derive(Deserialize)]
+struct ContactSheetResponse {
+    // Inputs the backend could not decode; their cells are left as placeholders
+    #[serde(default)]
+    failed_paths: Vec<String>,
+}
+
+fn validate(paths: &[String], columns: u32, thumb_size: u32) -> Result<(), String> {
+    if paths.is_empty() {
+        return Err("No images selected".to_string());
+    }
+    if columns == 0 || columns > MAX_COLUMNS {
+        return Err(format!("Columns must be between 1 and {}", MAX_COLUMNS));
+    }
+    if !(MIN_THUMB_SIZE..=MAX_THUMB_SIZE).contains(&thumb_size) {
+        return Err(format!(
+            "Thumbnail size must be between {} and {} pixels",
+            MIN_THUMB_SIZE, MAX_THUMB_SIZE
+        ));
+    }
+    Ok(())
+}
+
+// A sheet with some placeholder cells still succeeds with a warning; one
+// where nothing loaded is a failure
+fn sheet_result(
+    paths: Vec<String>,
+    failed: Vec<String>,
+    output_path: String,
+    processing_time_ms: u64,
+) -> ProcessingResult {
+    let loaded = paths.len().saturating_sub(failed.len());
+    let (warning, error) = if failed.is_empty() {
+        (None, None)
+    } else if loaded == 0 {
+        (None, Some("None of the selected images could be loaded".to_string()))
+    } else {
+        let warning = format!(
+            "{} image(s) could not be loaded and were left blank: {}",
+            failed.len(),
+            failed.join(", ")
+        );
+        (Some(warning), None)
+    };
+
+    ProcessingResult {
+        session_id: None,
+        success: error.is_none(),
+        input_path: String::new(),
+        input_paths: paths,
+        output_path,
+        operations_applied: loaded as u32,
+        processing_time_ms,
+        profile: ProcessingProfile::default(),
+        folder_id: None,
+        encoding: None,
+        models: Vec::new(),
+        output_sharpening: None,
+        warning,
+        error,
+    }
+}
+
+// Tauri commands
+#[tauri::command]
+pub async fn generate_contact_sheet(
+    paths: Vec<String>,
+    columns: u32,
+    thumb_size: u32,
+    output_path: String,
+    labels: Option<bool>,
+) -> Result<ProcessingResult, String> {
+    validate(&paths, columns, thumb_size)?;
+
+    let started = Instant::now();
+
+    // Missing files still get a cell so the grid keeps the selection order
+    let mut failed: Vec<String> = paths
+        .iter()
+        .filter(|path| !Path::new(path).exists())
+        .cloned()
+        .collect();
+
+    // Call Python backend
+    let client = reqwest::Client::new();
+    let response = client
+        .post("http://localhost:8888/image/contact-sheet")
+        .json(&ContactSheetRequest {
+            paths: &paths,
+            columns,
+            thumb_size,
+            labels: labels.unwrap_or(true),
+            output_path: &output_path,
+        })
+        .send()
+        .await
+        .map_err(|e| format!("Failed to connect to processing engine: {}", e))?;
+
+    if !response.status().is_success() {
+        let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+        return Err(format!("Processing engine error: {}", error_text));
+    }
+
+    let sheet = response
+        .json::<ContactSheetResponse>()
+        .await
+        .map_err(|e| format!("Invalid response from processing engine: {}", e))?;
+
+    for path in sheet.failed_paths {
+        if !failed.contains(&path) {
+            failed.push(path);
+        }
+    }
+
+    Ok(sheet_result(
+        paths,
+        failed,
+        output_path,
+        started.elapsed().as_millis() as u64,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn paths(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn columns_and_thumbnail_size_are_bounded() {
+        let selection = paths(&["/in/a.jpg"]);
+        assert!(validate(&selection, 4, 256).is_ok());
+        assert!(validate(&[], 4, 256).is_err());
+        assert!(validate(&selection, 0, 256).is_err());
+        assert!(validate(&selection, MAX_COLUMNS + 1, 256).is_err());
+        assert!(validate(&selection, 4, MIN_THUMB_SIZE - 1).is_err());
+        assert!(validate(&selection, 4, MAX_THUMB_SIZE + 1).is_err());
+    }
+
+    #[test]
+    fn inputs_are_listed_in_their_own_field() {
+        let selection = paths(&["/in/a.jpg", "/in/b.jpg"]);
+        let result = sheet_result(selection, Vec::new(), "/out/s.jpg".into(), 5);
+        assert!(result.success);
+        assert_eq!(result.input_path, "");
+        assert_eq!(result.input_paths, ["/in/a.jpg", "/in/b.jpg"]);
+        assert_eq!(result.operations_applied, 2);
+        assert!(result.warning.is_none());
+    }
+
+    #[test]
+    fn placeholder_cells_warn_and_an_empty_sheet_fails() {
+        let partial = sheet_result(
+            paths(&["/in/a.jpg", "/in/b.jpg"]),
+            paths(&["/in/b.jpg"]),
+            "/out/s.jpg".into(),
+            5,
+        );
+        assert!(partial.success);
+        assert_eq!(partial.operations_applied, 1);
+        assert_eq!(
+            partial.warning.unwrap(),
+            "1 image(s) could not be loaded and were left blank: /in/b.jpg"
+        );
+
+        let selection = paths(&["/in/a.jpg", "/in/b.jpg"]);
+        let empty = sheet_result(selection.clone(), selection, "/out/s.jpg".into(), 5);
+        assert!(!empty.success);
+        assert_eq!(empty.operations_applied, 0);
+        assert_eq!(empty.error.unwrap(), "None of the selected images could be loaded");
+    }
+}
@@ -0,0 +1,91 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+
+// Subsystems that report into the status strip
+pub const HOT_FOLDER: &str = "hot_folder";
+pub const PIPELINE: &str = "pipeline";
+pub const BACKEND: &str = "backend";
+pub const INGEST_SOURCE: &str = "ingest_source";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ErrorInfo {
+    pub message: String,
+    pub timestamp: String,
+}
+
+// One slot per subsystem, overwritten by each new error
+struct LastErrors(BTreeMap<&'static str, ErrorInfo>);
+
+impl LastErrors {
+    const fn new() -> Self {
+        Self(BTreeMap::new())
+    }
+
+    fn record(&mut self, subsystem: &'static str, message: &str, timestamp: String) {
+        let info = ErrorInfo {
+            message: message.to_string(),
+            timestamp,
+        };
+        self.0.insert(subsystem, info);
+    }
+
+    fn snapshot(&self) -> HashMap<String, ErrorInfo> {
+        self.0
+            .iter()
+            .map(|(subsystem, info)| (subsystem.to_string(), info.clone()))
+            .collect()
+    }
+}
+
+static LAST_ERRORS: Mutex<LastErrors> = Mutex::new(LastErrors::new());
+
+pub fn record_last_error(subsystem: &'static str, message: &str) {
+    let timestamp = chrono::Utc::now().to_rfc3339();
+    LAST_ERRORS.lock().unwrap().record(subsystem, message, timestamp);
+}
+
+// Tauri commands
+#[tauri::command]
+pub async fn get_last_errors() -> Result<HashMap<String, ErrorInfo>, String> {
+    Ok(LAST_ERRORS.lock().unwrap().snapshot())
+}
+
+#[tauri::command]
+pub async fn clear_last_errors() -> Result<(), String> {
+    LAST_ERRORS.lock().unwrap().0.clear();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn each_subsystem_keeps_only_its_latest_error() {
+        let mut errors = LastErrors::new();
+        errors.record(PIPELINE, "decode failed", "2026-10-15T09:00:00+00:00".to_string());
+        errors.record(BACKEND, "connection refused", "2026-10-15T09:00:01+00:00".to_string());
+        errors.record(PIPELINE, "timed out", "2026-10-15T09:00:02+00:00".to_string());
+
+        let snapshot = errors.snapshot();
+        assert_eq!(snapshot.len(), 2);
+        assert_eq!(snapshot["pipeline"].message, "timed out");
+        assert_eq!(snapshot["pipeline"].timestamp, "2026-10-15T09:00:02+00:00");
+        assert_eq!(snapshot["backend"].message, "connection refused");
+    }
+
+    #[test]
+    fn snapshot_serializes_keyed_by_subsystem() {
+        let mut errors = LastErrors::new();
+        assert!(errors.snapshot().is_empty());
+
+        errors.record(HOT_FOLDER, "watch failed", "2026-10-15T09:00:00+00:00".to_string());
+        assert_eq!(
+            serde_json::to_value(errors.snapshot()).unwrap(),
+            serde_json::json!({
+                "hot_folder": {"message": "watch failed", "timestamp": "2026-10-15T09:00:00+00:00"}
+            })
+        );
+    }
+}
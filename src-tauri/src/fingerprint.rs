@@ -0,0 +1,255 @@
+use crate::commands::get_image_info;
+use crate::pipeline::file_checksum;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Instant, SystemTime};
+
+// Side length of the grayscale thumbnail used for perceptual fingerprints
+const PERCEPTUAL_HASH_SIZE: u32 = 8;
+
+// Beyond this many entries the least recently computed one is dropped
+const MAX_CACHED_FINGERPRINTS: usize = 10_000;
+
+// Exact: sha256 of the file bytes plus dimensions; any byte change is a new
+// fingerprint. Perceptual: average hash of an 8x8 grayscale thumbnail plus
+// dimensions, so re-encodes and metadata edits of the same image still match.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FingerprintMode {
+    #[default]
+    Exact,
+    Perceptual,
+}
+
+struct CachedFingerprint {
+    modified: SystemTime,
+    fingerprint: String,
+    computed_at: Instant,
+}
+
+// Keyed by (path, mode); entries are reused while the file's mtime is unchanged
+static FINGERPRINT_CACHE: Mutex<BTreeMap<(String, FingerprintMode), CachedFingerprint>> =
+    Mutex::new(BTreeMap::new());
+
+#[derive(Serialize)]
+struct ThumbnailRequest<'a> {
+    path: &'a str,
+    width: u32,
+    height: u32,
+    grayscale: bool,
+}
+
+#[derive(Deserialize)]
+struct ThumbnailResponse {
+    pixels: Vec<u8>,
+}
+
+fn modified_time(path: &str) -> Result<SystemTime, String> {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .map_err(|e| format!("Failed to read {}: {}", path, e))
+}
+
+async fn average_hash(path: &str) -> Result<String, String> {
+    // Call Python backend
+    let client = reqwest::Client::new();
+    let response = client
+        .post("http://localhost:8888/image/thumbnail")
+        .json(&ThumbnailRequest {
+            path,
+            width: PERCEPTUAL_HASH_SIZE,
+            height: PERCEPTUAL_HASH_SIZE,
+            grayscale: true,
+        })
+        .send()
+        .await
+        .map_err(|e| format!("Failed to connect to processing engine: {}", e))?;
+
+    if !response.status().is_success() {
+        let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+        return Err(format!("Processing engine error: {}", error_text));
+    }
+
+    let thumbnail = response
+        .json::<ThumbnailResponse>()
+        .await
+        .map_err(|e| format!("Invalid response from processing engine: {}", e))?;
+
+    let expected = (PERCEPTUAL_HASH_SIZE * PERCEPTUAL_HASH_SIZE) as usize;
+    if thumbnail.pixels.len() != expected {
+        return Err(format!(
+            "Invalid response from processing engine: expected {} pixels, got {}",
+            expected,
+            thumbnail.pixels.len()
+        ));
+    }
+
+    Ok(hash_pixels(&thumbnail.pixels))
+}
+
+// One bit per pixel: set when brighter than the thumbnail's mean
+fn hash_pixels(pixels: &[u8]) -> String {
+    let mean = pixels.iter().map(|&p| p as u32).sum::<u32>() / pixels.len().max(1) as u32;
+    let hash = pixels
+        .iter()
+        .fold(0u64, |hash, &p| (hash << 1) | (p as u32 > mean) as u64);
+    format!("{:016x}", hash)
+}
+
+fn format_fingerprint(mode: FingerprintMode, width: u32, height: u32, hash: &str) -> String {
+    let prefix = match mode {
+        FingerprintMode::Exact => "exact",
+        FingerprintMode::Perceptual => "perceptual",
+    };
+    format!("{}:{}x{}:{}", prefix, width, height, hash)
+}
+
+fn cache_fingerprint(
+    cache: &mut BTreeMap<(String, FingerprintMode), CachedFingerprint>,
+    key: (String, FingerprintMode),
+    cached: CachedFingerprint,
+    max_entries: usize,
+) {
+    if cache.len() >= max_entries && !cache.contains_key(&key) {
+        let oldest = cache
+            .iter()
+            .min_by_key(|(_, cached)| cached.computed_at)
+            .map(|(key, _)| key.clone());
+        if let Some(oldest) = oldest {
+            cache.remove(&oldest);
+        }
+    }
+    cache.insert(key, cached);
+}
+
+async fn compute_fingerprint(path: &str, mode: FingerprintMode) -> Result<String, String> {
+    let info = get_image_info(path.to_string()).await?;
+    let hash = match mode {
+        FingerprintMode::Exact => file_checksum(Path::new(path)).await?,
+        FingerprintMode::Perceptual => average_hash(path).await?,
+    };
+    Ok(format_fingerprint(mode, info.width, info.height, &hash))
+}
+
+pub async fn fingerprint(path: &str, mode: FingerprintMode) -> Result<String, String> {
+    let modified = modified_time(path)?;
+    let key = (path.to_string(), mode);
+
+    if let Some(cached) = FINGERPRINT_CACHE.lock().unwrap().get(&key) {
+        if cached.modified == modified {
+            return Ok(cached.fingerprint.clone());
+        }
+    }
+
+    let fingerprint = compute_fingerprint(path, mode).await?;
+    cache_fingerprint(
+        &mut FINGERPRINT_CACHE.lock().unwrap(),
+        key,
+        CachedFingerprint {
+            modified,
+            fingerprint: fingerprint.clone(),
+            computed_at: Instant::now(),
+        },
+        MAX_CACHED_FINGERPRINTS,
+    );
+    Ok(fingerprint)
+}
+
+// Tauri commands
+#[tauri::command]
+pub async fn get_fingerprint(path: String, mode: Option<FingerprintMode>) -> Result<String, String> {
+    if !Path::new(&path).exists() {
+        return Err("File not found".to_string());
+    }
+    fingerprint(&path, mode.unwrap_or_default()).await
+}
+
+// Searches files fingerprinted so far; entries whose file changed or
+// disappeared since are dropped rather than reported
+#[tauri::command]
+pub async fn find_by_fingerprint(fingerprint: String) -> Result<Vec<String>, String> {
+    let mut cache = FINGERPRINT_CACHE.lock().unwrap();
+    cache.retain(|(path, _), cached| modified_time(path) == Ok(cached.modified));
+
+    let mut matches: Vec<String> = cache
+        .iter()
+        .filter(|(_, cached)| cached.fingerprint == fingerprint)
+        .map(|((path, _), _)| path.clone())
+        .collect();
+    matches.dedup();
+    Ok(matches)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cached(fingerprint: &str) -> CachedFingerprint {
+        CachedFingerprint {
+            modified: SystemTime::UNIX_EPOCH,
+            fingerprint: fingerprint.to_string(),
+            computed_at: Instant::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn identical_files_share_a_fingerprint() {
+        let dir = std::env::temp_dir().join(format!("nstant-fingerprint-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let bytes = include_bytes!("../assets/smoke-test.png");
+        let first = dir.join("a.png").to_string_lossy().to_string();
+        let copy = dir.join("copy of a.png").to_string_lossy().to_string();
+        let other = dir.join("b.png").to_string_lossy().to_string();
+        std::fs::write(&first, bytes).unwrap();
+        std::fs::write(&copy, bytes).unwrap();
+        std::fs::write(&other, [bytes.as_slice(), b"\0"].concat()).unwrap();
+
+        let mut fingerprints = Vec::new();
+        for path in [&first, &copy, &other] {
+            let hash = file_checksum(Path::new(path)).await.unwrap();
+            let fingerprint = format_fingerprint(FingerprintMode::Exact, 8, 8, &hash);
+            FINGERPRINT_CACHE.lock().unwrap().insert(
+                (path.clone(), FingerprintMode::Exact),
+                CachedFingerprint {
+                    modified: modified_time(path).unwrap(),
+                    ..cached(&fingerprint)
+                },
+            );
+            fingerprints.push(fingerprint);
+        }
+        assert_eq!(fingerprints[0], fingerprints[1]);
+        assert_ne!(fingerprints[0], fingerprints[2]);
+
+        let mut matches = find_by_fingerprint(fingerprints[0].clone()).await.unwrap();
+        matches.sort();
+        assert_eq!(matches, [first, copy]);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn perceptual_hash_ignores_uniform_brightness_changes() {
+        let pixels: Vec<u8> = (0..64).map(|i| (i * 3) as u8).collect();
+        let brighter: Vec<u8> = pixels.iter().map(|&p| p + 20).collect();
+        assert_eq!(hash_pixels(&pixels), hash_pixels(&brighter));
+        assert_eq!(hash_pixels(&pixels), "00000000ffffffff");
+    }
+
+    #[test]
+    fn cache_drops_the_oldest_entry_when_full() {
+        let mut cache = BTreeMap::new();
+        for path in ["c.jpg", "a.jpg", "b.jpg"] {
+            let key = (path.to_string(), FingerprintMode::Exact);
+            cache_fingerprint(&mut cache, key, cached(path), 2);
+        }
+        let paths: Vec<&str> = cache.keys().map(|(path, _)| path.as_str()).collect();
+        assert_eq!(paths, ["a.jpg", "b.jpg"]);
+
+        // Refreshing an existing entry doesn't evict anything
+        let key = ("a.jpg".to_string(), FingerprintMode::Exact);
+        cache_fingerprint(&mut cache, key, cached("a2"), 2);
+        assert_eq!(cache.len(), 2);
+    }
+}
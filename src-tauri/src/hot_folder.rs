@@ -1,12 +1,17 @@
-use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use crate::commands::{ensure_backend_storage, fetch_supported_formats};
+use crate::errors;
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
 use notify_debouncer_full::{new_debouncer, DebounceEventResult, Debouncer, FileIdMap};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::path::Path;
+use std::collections::{HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
+use sysinfo::System;
 use tauri::{AppHandle, Emitter, State};
 use tokio::sync::mpsc;
+use tokio::time::Instant;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HotFolderConfig {
@@ -15,6 +20,51 @@ pub struct HotFolderConfig {
     pub enabled: bool,
     pub extensions: Vec<String>,
     pub stability_timeout: u64, // milliseconds
+    // Throttle submissions while system CPU load (percent) is above this; off when unset
+    #[serde(default)]
+    pub cpu_throttle_threshold: Option<f32>,
+    // Group rapid sequential arrivals into bursts; off when unset
+    #[serde(default)]
+    pub burst_detection: Option<BurstConfig>,
+    // Per-kind overrides of stability_timeout (milliseconds). The debouncer runs at the
+    // shortest of these and events of slower kinds are held for the difference, so e.g.
+    // creates can be delivered promptly while modifies wait longer. Unset falls back to
+    // stability_timeout.
+    #[serde(default)]
+    pub create_timeout: Option<u64>,
+    #[serde(default)]
+    pub modify_timeout: Option<u64>,
+    // Extensions are checked against the backend's input formats on start;
+    // unsupported ones are reported, or rejected when strict
+    #[serde(default)]
+    pub skip_format_check: bool,
+    #[serde(default)]
+    pub strict_format_check: bool,
+    // Submit this folder's files to its own backend instead of the default one
+    #[serde(default)]
+    pub backend_url: Option<String>,
+    // Tethered capture: skip the capture software's temp files, pick up the final
+    // name on rename, and submit at "tether" priority. tether_temp_patterns extends
+    // TETHER_TEMP_PATTERNS.
+    #[serde(default)]
+    pub tether_mode: bool,
+    #[serde(default)]
+    pub tether_temp_patterns: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BurstConfig {
+    pub window_ms: u64, // max gap between consecutive files in a burst
+    pub max_group_size: usize,
+    #[serde(default = "default_require_sequential_names")]
+    pub require_sequential_names: bool,
+    // Hold submissions until the burst closes, then submit the group together
+    #[serde(default)]
+    pub submit_as_batch: bool,
+}
+
+fn default_require_sequential_names() -> bool {
+    true
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -25,9 +75,711 @@ pub struct WatcherEvent {
     pub timestamp: String,
 }
 
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FolderStats {
+    pub submitted: u64,
+    pub completed: u64,
+    pub failed: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThrottleEvent {
+    pub folder_id: String,
+    pub cpu_load: f32,
+    pub threshold: f32,
+    pub timestamp: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BurstEvent {
+    pub folder_id: String,
+    pub files: Vec<String>,
+    pub timestamp: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatcherError {
+    pub folder_id: String,
+    pub message: String,
+    pub timestamp: String,
+}
+
+// Keep only the most recent watcher errors around for diagnostics
+const MAX_RECENT_ERRORS: usize = 50;
+
+// Names tether software writes before renaming to the final file, matched
+// case-insensitively; a single '*' matches any run of characters
+const TETHER_TEMP_PATTERNS: [&str; 7] =
+    ["*.tmp", "*.temp", "*.part", "*.partial", "*.download", "~*", ".*"];
+
+fn matches_pattern(name: &str, pattern: &str) -> bool {
+    match pattern.split_once('*') {
+        Some((prefix, suffix)) => {
+            name.len() >= prefix.len() + suffix.len()
+                && name.starts_with(prefix)
+                && name.ends_with(suffix)
+        }
+        None => name == pattern,
+    }
+}
+
+fn is_tether_temp(path: &Path, patterns: &[String]) -> bool {
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    TETHER_TEMP_PATTERNS
+        .iter()
+        .copied()
+        .chain(patterns.iter().map(String::as_str))
+        .any(|pattern| matches_pattern(&name, &pattern.to_lowercase()))
+}
+
+// Renames carry [from, to]; tether captures land under the final name, and
+// the capture software's temp files are skipped entirely
+fn event_path<'a>(paths: &'a [PathBuf], tether_patterns: Option<&[String]>) -> Option<&'a Path> {
+    match tether_patterns {
+        Some(patterns) => paths
+            .last()
+            .filter(|path| !is_tether_temp(path, patterns))
+            .map(PathBuf::as_path),
+        None => paths.first().map(PathBuf::as_path),
+    }
+}
+
+// Each watcher holds OS watch handles and a task; cap how many run at once
+const DEFAULT_MAX_ACTIVE_WATCHERS: usize = 32;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatcherCapacity {
+    pub max_active: usize,
+    pub active: usize,
+}
+
+// Used by folders without their own backend_url
+pub const DEFAULT_BACKEND_URL: &str = "http://localhost:8888";
+
+// Inter-submit delay while throttled, doubling up to the max until load drops
+const THROTTLE_BASE_DELAY: Duration = Duration::from_millis(250);
+const THROTTLE_MAX_DELAY: Duration = Duration::from_secs(5);
+
+// The throttle's decision, kept apart from CPU sampling: over the threshold
+// the inter-submit delay starts at the base and doubles up to the max; at or
+// under it the throttle clears
+#[derive(Debug, PartialEq)]
+struct ThrottleState {
+    threshold: f32,
+    // Current inter-submit delay; None while load is under the threshold
+    delay: Option<Duration>,
+}
+
+impl ThrottleState {
+    // Delay before the next submit for this load sample, and whether it starts
+    // a throttled stretch
+    fn next_delay(&mut self, cpu_load: f32) -> Option<(Duration, bool)> {
+        if cpu_load <= self.threshold {
+            self.delay = None;
+            return None;
+        }
+        let (delay, started) = match self.delay {
+            Some(delay) => ((delay * 2).min(THROTTLE_MAX_DELAY), false),
+            None => (THROTTLE_BASE_DELAY, true),
+        };
+        self.delay = Some(delay);
+        Some((delay, started))
+    }
+}
+
+pub(crate) struct LoadThrottle {
+    system: System,
+    state: ThrottleState,
+}
+
+impl LoadThrottle {
+    pub(crate) fn new(threshold: f32) -> Self {
+        let mut system = System::new();
+        // Prime the CPU counters so the first sample has a baseline
+        system.refresh_cpu();
+        Self {
+            system,
+            state: ThrottleState {
+                threshold,
+                delay: None,
+            },
+        }
+    }
+
+    fn cpu_load(&mut self) -> f32 {
+        self.system.refresh_cpu();
+        self.system.global_cpu_info().cpu_usage()
+    }
+
+    // Called before each submit: sleeps once for the current delay while the
+    // machine is busy
+    async fn pace(&mut self, folder_id: &str, app_handle: &AppHandle) {
+        let cpu_load = self.cpu_load();
+        let Some((delay, started)) = self.state.next_delay(cpu_load) else {
+            return;
+        };
+        if started {
+            let _ = app_handle.emit(
+                "hot-folder-throttled",
+                &ThrottleEvent {
+                    folder_id: folder_id.to_string(),
+                    cpu_load,
+                    threshold: self.state.threshold,
+                    timestamp: chrono::Utc::now().to_rfc3339(),
+                },
+            );
+        }
+        tokio::time::sleep(delay).await;
+    }
+}
+
+// Upper bound for any debounce timeout, per kind or global
+const MAX_EVENT_TIMEOUT_MS: u64 = 10 * 60 * 1000;
+
+// How long to hold each kind of event beyond the debouncer's own timeout
+struct EventDelays {
+    create: Duration,
+    modify: Duration,
+    other: Duration,
+}
+
+impl EventDelays {
+    // Returns the debouncer timeout together with the extra per-kind delays
+    fn from_config(config: &HotFolderConfig) -> (Duration, Self) {
+        let other = config.stability_timeout;
+        let create = config.create_timeout.unwrap_or(other);
+        let modify = config.modify_timeout.unwrap_or(other);
+        let base = other.min(create).min(modify);
+
+        let extra = |timeout: u64| Duration::from_millis(timeout - base);
+        (
+            Duration::from_millis(base),
+            Self {
+                create: extra(create),
+                modify: extra(modify),
+                other: extra(other),
+            },
+        )
+    }
+
+    fn for_kind(&self, kind: &EventKind) -> Duration {
+        match kind {
+            EventKind::Create(_) => self.create,
+            EventKind::Modify(_) => self.modify,
+            _ => self.other,
+        }
+    }
+}
+
+// Files whose events are being held until their per-kind delay elapses
+#[derive(Default)]
+struct HeldEvents {
+    due: HashMap<String, Instant>,
+}
+
+impl HeldEvents {
+    fn hold(&mut self, path: &str, until: Instant) {
+        let due = self.due.entry(path.to_string()).or_insert(until);
+        *due = (*due).max(until);
+    }
+
+    fn is_held(&self, path: &str) -> bool {
+        self.due.contains_key(path)
+    }
+
+    fn next_due(&self) -> Option<Instant> {
+        self.due.values().min().copied()
+    }
+
+    fn take_due(&mut self, now: Instant) -> Vec<String> {
+        let mut ready: Vec<(String, Instant)> = self
+            .due
+            .iter()
+            .filter(|(_, due)| **due <= now)
+            .map(|(path, due)| (path.clone(), *due))
+            .collect();
+        ready.sort_by_key(|(_, due)| *due);
+        for (path, _) in &ready {
+            self.due.remove(path);
+        }
+        ready.into_iter().map(|(path, _)| path).collect()
+    }
+
+    fn take_all(&mut self) -> Vec<String> {
+        let mut all: Vec<(String, Instant)> = self.due.drain().collect();
+        all.sort_by_key(|(_, due)| *due);
+        all.into_iter().map(|(path, _)| path).collect()
+    }
+}
+
+// A burst never stays open longer than this, however steady the arrivals
+const MAX_BURST_SPAN: Duration = Duration::from_secs(30);
+
+// Split "IMG_0042" into ("IMG_", 42) for sequence detection
+fn sequence_number(path: &str) -> Option<(String, u64)> {
+    let stem = Path::new(path).file_stem()?.to_string_lossy().to_string();
+    let prefix = stem.trim_end_matches(|c: char| c.is_ascii_digit());
+    let number = stem[prefix.len()..].parse().ok()?;
+    Some((prefix.to_string(), number))
+}
+
+struct BurstTracker {
+    config: BurstConfig,
+    files: Vec<String>,
+    last_sequence: Option<(String, u64)>,
+    started: Instant,
+    last_seen: Instant,
+}
+
+impl BurstTracker {
+    fn new(config: BurstConfig) -> Self {
+        let now = Instant::now();
+        Self {
+            config,
+            files: Vec::new(),
+            last_sequence: None,
+            started: now,
+            last_seen: now,
+        }
+    }
+
+    fn deadline(&self) -> Option<Instant> {
+        if self.files.is_empty() {
+            return None;
+        }
+        let window = Duration::from_millis(self.config.window_ms);
+        Some((self.last_seen + window).min(self.started + MAX_BURST_SPAN))
+    }
+
+    fn take(&mut self) -> Vec<String> {
+        self.last_sequence = None;
+        std::mem::take(&mut self.files)
+    }
+
+    // Adds a file arriving at `now`, returning any group that closed as a result
+    fn push(&mut self, path: &str, now: Instant) -> Vec<Vec<String>> {
+        let sequence = sequence_number(path);
+        let mut closed = Vec::new();
+
+        if !self.files.is_empty() {
+            let in_window = self.deadline().is_some_and(|deadline| now <= deadline);
+            let in_sequence = !self.config.require_sequential_names
+                || matches!(
+                    (&self.last_sequence, &sequence),
+                    (Some((last_prefix, last)), Some((prefix, number)))
+                        if last_prefix == prefix && *number == last + 1
+                );
+            if !(in_window && in_sequence) {
+                closed.push(self.take());
+            }
+        }
+
+        if self.files.is_empty() {
+            self.started = now;
+        }
+        self.files.push(path.to_string());
+        self.last_sequence = sequence;
+        self.last_seen = now;
+
+        if self.files.len() >= self.config.max_group_size {
+            closed.push(self.take());
+        }
+        closed
+    }
+}
+
+// Per-folder state used by the event task when handling detected files
+struct FolderEvents {
+    folder_id: String,
+    app_handle: AppHandle,
+    events: Arc<EventCoalescer>,
+    // Paths queued for the folder's submit worker
+    submissions: mpsc::UnboundedSender<String>,
+}
+
+impl FolderEvents {
+    fn file_added(&mut self, path: &str, bursts: Option<&mut BurstTracker>) {
+        let watcher_event = WatcherEvent {
+            event_type: "file_added".to_string(),
+            path: path.to_string(),
+            folder_id: self.folder_id.clone(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+        };
+
+        match bursts {
+            Some(bursts) => {
+                let submit_as_batch = bursts.config.submit_as_batch;
+                if !submit_as_batch {
+                    self.submit(path);
+                }
+                for group in bursts.push(path, Instant::now()) {
+                    self.close_burst(group, submit_as_batch);
+                }
+            }
+            None => self.submit(path),
+        }
+
+        // Emit to frontend
+        self.events.emit(watcher_event);
+    }
+
+    fn close_burst(&mut self, files: Vec<String>, submit: bool) {
+        if submit {
+            for path in &files {
+                self.submit(path);
+            }
+        }
+
+        // A lone file is not a burst
+        if files.len() > 1 {
+            let _ = self.app_handle.emit(
+                "hot-folder-burst",
+                &BurstEvent {
+                    folder_id: self.folder_id.clone(),
+                    files,
+                    timestamp: chrono::Utc::now().to_rfc3339(),
+                },
+            );
+        }
+    }
+
+    fn submit(&mut self, path: &str) {
+        let _ = self.submissions.send(path.to_string());
+    }
+}
+
+// Submits a folder's files in arrival order off the event task, so throttling
+// and slow backends never hold up detection, held events or burst closing
+fn spawn_submit_worker(
+    folder_id: String,
+    submitter: Submitter,
+    hold: Arc<Mutex<SubmissionHold>>,
+    mut throttle: Option<LoadThrottle>,
+    app_handle: AppHandle,
+) -> mpsc::UnboundedSender<String> {
+    let (tx, mut rx) = mpsc::unbounded_channel::<String>();
+    tokio::spawn(async move {
+        while let Some(path) = rx.recv().await {
+            let submission = HeldSubmission {
+                path,
+                folder_id: folder_id.clone(),
+                submitter: submitter.clone(),
+            };
+            submit_unless_held(&hold, submission, throttle.as_mut(), &app_handle).await;
+        }
+    });
+    tx
+}
+
+// The path every submission takes, whatever its source: buffered while
+// submissions are on hold, otherwise paced by the load throttle and posted.
+// None when the file was buffered for the next release.
+async fn submit_unless_held(
+    hold: &Mutex<SubmissionHold>,
+    submission: HeldSubmission,
+    throttle: Option<&mut LoadThrottle>,
+    app_handle: &AppHandle,
+) -> Option<bool> {
+    let HeldSubmission {
+        path,
+        folder_id,
+        submitter,
+    } = hold.lock().unwrap().buffer(submission)?;
+
+    // Back off while the machine is busy with interactive work
+    if let Some(throttle) = throttle {
+        throttle.pace(&folder_id, app_handle).await;
+    }
+
+    Some(submitter.submit(&path, &folder_id).await)
+}
+
+// Posts files to one backend's queue; clones share the pooled client for that URL
+#[derive(Clone)]
+struct Submitter {
+    backend_url: String,
+    priority: &'static str,
+    client: reqwest::Client,
+    errors: Arc<Mutex<VecDeque<WatcherError>>>,
+    stats: Arc<Mutex<HashMap<String, FolderStats>>>,
+}
+
+impl Submitter {
+    // folder_id comes back on the backend's job events for per-folder stats
+    fn queue_item(&self, path: &str, folder_id: &str) -> serde_json::Value {
+        serde_json::json!({
+            "path": path,
+            "folder_id": folder_id,
+            "priority": self.priority
+        })
+    }
+
+    // Returns whether the backend accepted the request
+    async fn submit(&self, path: &str, folder_id: &str) -> bool {
+        // Send to Python backend
+        let error = match self
+            .client
+            .post(format!("{}/queue/add", self.backend_url))
+            .json(&self.queue_item(path, folder_id))
+            .send()
+            .await
+        {
+            Ok(response) if response.status().is_success() => {
+                self.stats
+                    .lock()
+                    .unwrap()
+                    .entry(folder_id.to_string())
+                    .or_default()
+                    .submitted += 1;
+                return true;
+            }
+            Ok(response) => {
+                let status = response.status();
+                let error_text = response.text().await.unwrap_or_else(|_| status.to_string());
+                format!("Processing engine error: {}", error_text)
+            }
+            Err(e) => e.to_string(),
+        };
+
+        record_error(
+            &self.errors,
+            folder_id,
+            format!("Failed to submit {} to {}: {}", path, self.backend_url, error),
+        );
+        false
+    }
+}
+
+pub(crate) fn validate_throttle_threshold(threshold: Option<f32>) -> Result<(), String> {
+    match threshold {
+        Some(threshold) if !(threshold > 0.0 && threshold <= 100.0) => {
+            Err("CPU throttle threshold must be between 0 and 100".to_string())
+        }
+        _ => Ok(()),
+    }
+}
+
+fn validate_backend_url(url: &str) -> Result<String, String> {
+    let parsed = reqwest::Url::parse(url).map_err(|e| format!("Invalid backend URL: {}", e))?;
+    if parsed.scheme() != "http" && parsed.scheme() != "https" {
+        return Err("Backend URL must use http or https".to_string());
+    }
+    Ok(url.trim_end_matches('/').to_string())
+}
+
+// Restarting an already-active folder reuses its slot
+fn check_watcher_slot<W>(
+    watchers: &HashMap<String, W>,
+    folder_id: &str,
+    max_active: usize,
+) -> Result<(), String> {
+    if !watchers.contains_key(folder_id) && watchers.len() >= max_active {
+        return Err(format!("Too many active watchers (limit {})", max_active));
+    }
+    Ok(())
+}
+
+// The backend a folder's files are checked against and submitted to
+fn backend_for(config: &HotFolderConfig) -> Result<String, String> {
+    match &config.backend_url {
+        Some(url) => validate_backend_url(url),
+        None => Ok(DEFAULT_BACKEND_URL.to_string()),
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventCoalescing {
+    // Above this many events in a second, events are batched instead
+    pub max_events_per_second: u32,
+    pub batch_interval_ms: u64,
+}
+
+impl Default for EventCoalescing {
+    fn default() -> Self {
+        Self {
+            max_events_per_second: 50,
+            batch_interval_ms: 250,
+        }
+    }
+}
+
+const MAX_BATCH_INTERVAL_MS: u64 = 5000;
+
+#[derive(Debug)]
+enum Delivery {
+    Now(WatcherEvent),
+    // Held for the next batch; the first event held starts the flush timer
+    Pending { start_flush: bool },
+}
+
+#[derive(Default)]
+struct CoalesceState {
+    window_start: Option<Instant>,
+    window_count: u32,
+    pending: Vec<WatcherEvent>,
+}
+
+impl CoalesceState {
+    fn accept(&mut self, event: WatcherEvent, now: Instant, max_per_second: u32) -> Delivery {
+        match self.window_start {
+            Some(start) if now.duration_since(start) < Duration::from_secs(1) => {
+                self.window_count += 1;
+            }
+            _ => {
+                self.window_start = Some(now);
+                self.window_count = 1;
+            }
+        }
+
+        // Once batching, keep batching until the pending events are flushed
+        if self.pending.is_empty() && self.window_count <= max_per_second {
+            return Delivery::Now(event);
+        }
+        self.pending.push(event);
+        Delivery::Pending {
+            start_flush: self.pending.len() == 1,
+        }
+    }
+}
+
+// Emits hot-folder-event individually at normal rates and switches to
+// periodic hot-folder-events-batch during storms. Events are only ever
+// delayed, never dropped, and keep their order: every emit happens under the
+// state lock, so a flushed batch can't be overtaken by a later single event.
+struct EventCoalescer {
+    settings: Mutex<EventCoalescing>,
+    state: Mutex<CoalesceState>,
+    app_handle: AppHandle,
+}
+
+impl EventCoalescer {
+    fn emit(self: &Arc<Self>, event: WatcherEvent) {
+        let settings = self.settings.lock().unwrap().clone();
+        let mut state = self.state.lock().unwrap();
+
+        match state.accept(event, Instant::now(), settings.max_events_per_second) {
+            Delivery::Now(event) => {
+                let _ = self.app_handle.emit("hot-folder-event", &event);
+            }
+            Delivery::Pending { start_flush: true } => {
+                let coalescer = self.clone();
+                tokio::spawn(async move {
+                    tokio::time::sleep(Duration::from_millis(settings.batch_interval_ms)).await;
+                    let mut state = coalescer.state.lock().unwrap();
+                    let batch = std::mem::take(&mut state.pending);
+                    let _ = coalescer.app_handle.emit("hot-folder-events-batch", &batch);
+                });
+            }
+            Delivery::Pending { start_flush: false } => {}
+        }
+    }
+}
+
+struct HeldSubmission {
+    path: String,
+    folder_id: String,
+    submitter: Submitter,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum HoldKind {
+    Manual,
+    Scheduled,
+}
+
+// Global hold on backend submission; detection and frontend events carry on.
+// Manual and scheduled holds are tracked separately so quiet hours ending
+// never lifts a hold the user placed. New arrivals keep buffering while a
+// release is still flushing, so they queue behind the held ones instead of
+// overtaking them.
+#[derive(Default)]
+struct SubmissionHold {
+    manual: bool,
+    scheduled: bool,
+    flushing: bool,
+    buffered: VecDeque<HeldSubmission>,
+}
+
+impl SubmissionHold {
+    fn active(&self) -> bool {
+        self.manual || self.scheduled
+    }
+
+    fn hold(&mut self, kind: HoldKind) {
+        match kind {
+            HoldKind::Manual => self.manual = true,
+            HoldKind::Scheduled => self.scheduled = true,
+        }
+    }
+
+    // Hands the submission back when it can go straight to the backend
+    fn buffer(&mut self, held: HeldSubmission) -> Option<HeldSubmission> {
+        if self.active() || self.flushing {
+            self.buffered.push_back(held);
+            return None;
+        }
+        Some(held)
+    }
+
+    // Lifts one kind of hold; true when the caller should drain the buffer,
+    // false while the other kind still holds or another release is draining
+    fn release(&mut self, kind: HoldKind) -> bool {
+        match kind {
+            HoldKind::Manual => self.manual = false,
+            HoldKind::Scheduled => self.scheduled = false,
+        }
+        if self.active() || self.flushing {
+            return false;
+        }
+        self.flushing = true;
+        true
+    }
+
+    // Stops at an empty buffer or a new hold, which ends the flush
+    fn next_to_flush(&mut self) -> Option<HeldSubmission> {
+        let next = if self.active() {
+            None
+        } else {
+            self.buffered.pop_front()
+        };
+        if next.is_none() {
+            self.flushing = false;
+        }
+        next
+    }
+}
+
+// Ends the flush if a release is dropped partway through, so buffered files
+// aren't stuck behind a flush that will never finish
+struct FlushGuard<'a> {
+    hold: &'a Mutex<SubmissionHold>,
+    finished: bool,
+}
+
+impl Drop for FlushGuard<'_> {
+    fn drop(&mut self) {
+        if self.finished {
+            return;
+        }
+        if let Ok(mut hold) = self.hold.lock() {
+            hold.flushing = false;
+        }
+    }
+}
+
 pub struct HotFolderManager {
     watchers: Arc<Mutex<HashMap<String, Debouncer<RecommendedWatcher, FileIdMap>>>>,
     configs: Arc<Mutex<HashMap<String, HotFolderConfig>>>,
+    recent_errors: Arc<Mutex<VecDeque<WatcherError>>>,
+    stats: Arc<Mutex<HashMap<String, FolderStats>>>,
+    hold: Arc<Mutex<SubmissionHold>>,
+    // One pooled client per distinct backend URL
+    clients: Mutex<HashMap<String, reqwest::Client>>,
+    max_active_watchers: AtomicUsize,
+    events: Arc<EventCoalescer>,
     app_handle: AppHandle,
 }
 
@@ -36,30 +788,100 @@ impl HotFolderManager {
         Self {
             watchers: Arc::new(Mutex::new(HashMap::new())),
             configs: Arc::new(Mutex::new(HashMap::new())),
+            recent_errors: Arc::new(Mutex::new(VecDeque::new())),
+            stats: Arc::new(Mutex::new(HashMap::new())),
+            hold: Arc::new(Mutex::new(SubmissionHold::default())),
+            clients: Mutex::new(HashMap::new()),
+            max_active_watchers: AtomicUsize::new(DEFAULT_MAX_ACTIVE_WATCHERS),
+            events: Arc::new(EventCoalescer {
+                settings: Mutex::new(EventCoalescing::default()),
+                state: Mutex::new(CoalesceState::default()),
+                app_handle: app_handle.clone(),
+            }),
             app_handle,
         }
     }
 
+    fn submitter(&self, backend_url: &str, priority: &'static str) -> Submitter {
+        let client = self
+            .clients
+            .lock()
+            .unwrap()
+            .entry(backend_url.to_string())
+            .or_default()
+            .clone();
+
+        Submitter {
+            backend_url: backend_url.to_string(),
+            priority,
+            client,
+            errors: self.recent_errors.clone(),
+            stats: self.stats.clone(),
+        }
+    }
+
     pub fn start_watching(&self, config: HotFolderConfig) -> Result<(), String> {
+        // Checked up front to fail fast, and again when the watcher is stored
+        let max_active = self.max_active_watchers.load(Ordering::Relaxed);
+        check_watcher_slot(&self.watchers.lock().unwrap(), &config.id, max_active)?;
+        validate_throttle_threshold(config.cpu_throttle_threshold)?;
+        for timeout in [Some(config.stability_timeout), config.create_timeout, config.modify_timeout]
+            .into_iter()
+            .flatten()
+        {
+            if timeout > MAX_EVENT_TIMEOUT_MS {
+                return Err("Event timeouts must be at most 10 minutes".to_string());
+            }
+        }
+        if let Some(burst) = &config.burst_detection {
+            if burst.window_ms == 0 || burst.window_ms > MAX_BURST_SPAN.as_millis() as u64 {
+                return Err("Burst window must be between 1ms and 30s".to_string());
+            }
+            if burst.max_group_size < 2 {
+                return Err("Burst group size must be at least 2".to_string());
+            }
+        }
+
+        if config
+            .tether_temp_patterns
+            .iter()
+            .any(|pattern| pattern.is_empty() || pattern.matches('*').count() > 1)
+        {
+            return Err("Tether temp patterns must be non-empty with at most one '*'".to_string());
+        }
+
+        let backend_url = backend_for(&config)?;
+
         let folder_id = config.id.clone();
         let folder_path = config.path.clone();
         let extensions = config.extensions.clone();
         let app_handle = self.app_handle.clone();
-        let stability_timeout = Duration::from_millis(config.stability_timeout);
+        let (debounce_timeout, delays) = EventDelays::from_config(&config);
+        let throttle = config.cpu_throttle_threshold.map(LoadThrottle::new);
+        let burst_detection = config.burst_detection.clone();
+        let tether_patterns = config.tether_mode.then(|| config.tether_temp_patterns.clone());
+        let priority = if config.tether_mode { "tether" } else { "normal" };
 
         // Create a channel for events
         let (tx, mut rx) = mpsc::unbounded_channel();
 
         // Create debounced watcher
+        let watcher_errors = self.recent_errors.clone();
+        let watcher_folder_id = folder_id.clone();
         let mut debouncer = new_debouncer(
-            stability_timeout,
+            debounce_timeout,
             None,
-            move |result: DebounceEventResult| {
-                if let Ok(events) = result {
+            move |result: DebounceEventResult| match result {
+                Ok(events) => {
                     for event in events {
                         let _ = tx.send(event);
                     }
                 }
+                Err(errors) => {
+                    for error in errors {
+                        record_error(&watcher_errors, &watcher_folder_id, error.to_string());
+                    }
+                }
             },
         )
         .map_err(|e| format!("Failed to create watcher: {}", e))?;
@@ -70,52 +892,90 @@ impl HotFolderManager {
             .watch(Path::new(&folder_path), RecursiveMode::Recursive)
             .map_err(|e| format!("Failed to watch folder: {}", e))?;
 
+        // Store watcher, re-checking the cap under the same lock so concurrent
+        // starts can't both take the last slot
+        {
+            let mut watchers = self.watchers.lock().unwrap();
+            check_watcher_slot(&watchers, &folder_id, max_active)?;
+            watchers.insert(folder_id.clone(), debouncer);
+        }
+
         // Store config
         self.configs.lock().unwrap().insert(folder_id.clone(), config);
 
-        // Store watcher
-        self.watchers
-            .lock()
-            .unwrap()
-            .insert(folder_id.clone(), debouncer);
-
         // Spawn task to handle events
-        let folder_id_clone = folder_id.clone();
+        let submissions = spawn_submit_worker(
+            folder_id.clone(),
+            self.submitter(&backend_url, priority),
+            self.hold.clone(),
+            throttle,
+            app_handle.clone(),
+        );
+        let mut folder_events = FolderEvents {
+            folder_id: folder_id.clone(),
+            app_handle,
+            events: self.events.clone(),
+            submissions,
+        };
+        let mut bursts = burst_detection.map(BurstTracker::new);
+        let mut held = HeldEvents::default();
         tokio::spawn(async move {
-            while let Some(event) = rx.recv().await {
-                if let Some(paths) = event.paths.first() {
+            loop {
+                // Wake up to release held events or close an open burst
+                let deadline = [held.next_due(), bursts.as_ref().and_then(|bursts| bursts.deadline())]
+                    .into_iter()
+                    .flatten()
+                    .min();
+                let event = match deadline {
+                    Some(deadline) => tokio::select! {
+                        event = rx.recv() => event,
+                        _ = tokio::time::sleep_until(deadline) => {
+                            let now = Instant::now();
+                            for path in held.take_due(now) {
+                                folder_events.file_added(&path, bursts.as_mut());
+                            }
+                            if let Some(bursts) = bursts.as_mut() {
+                                if bursts.deadline().is_some_and(|deadline| deadline <= now) {
+                                    let group = bursts.take();
+                                    folder_events.close_burst(group, bursts.config.submit_as_batch);
+                                }
+                            }
+                            continue;
+                        }
+                    },
+                    None => rx.recv().await,
+                };
+
+                let Some(event) = event else {
+                    break;
+                };
+
+                if let Some(paths) = event_path(&event.paths, tether_patterns.as_deref()) {
                     let path_str = paths.to_string_lossy().to_string();
-                    
+
                     // Check if file has valid extension
                     if let Some(ext) = paths.extension() {
                         let ext_str = ext.to_string_lossy().to_lowercase();
                         if extensions.is_empty() || extensions.contains(&ext_str) {
-                            // Emit event to frontend
-                            let watcher_event = WatcherEvent {
-                                event_type: "file_added".to_string(),
-                                path: path_str.clone(),
-                                folder_id: folder_id_clone.clone(),
-                                timestamp: chrono::Utc::now().to_rfc3339(),
-                            };
-
-                            // Send to Python backend
-                            let client = reqwest::Client::new();
-                            let _ = client
-                                .post("http://localhost:8888/queue/add")
-                                .json(&serde_json::json!({
-                                    "path": path_str,
-                                    "folder_id": folder_id_clone,
-                                    "priority": "normal"
-                                }))
-                                .send()
-                                .await;
-
-                            // Emit to frontend
-                            let _ = app_handle.emit("hot-folder-event", &watcher_event);
+                            let delay = delays.for_kind(&event.kind);
+                            if delay.is_zero() && !held.is_held(&path_str) {
+                                folder_events.file_added(&path_str, bursts.as_mut());
+                            } else {
+                                held.hold(&path_str, Instant::now() + delay);
+                            }
                         }
                     }
                 }
             }
+
+            // Watcher stopped; flush held events and any open burst
+            for path in held.take_all() {
+                folder_events.file_added(&path, bursts.as_mut());
+            }
+            if let Some(bursts) = bursts.as_mut() {
+                let group = bursts.take();
+                folder_events.close_burst(group, bursts.config.submit_as_batch);
+            }
         });
 
         Ok(())
@@ -127,13 +987,267 @@ impl HotFolderManager {
         Ok(())
     }
 
+    pub fn watcher_capacity(&self) -> WatcherCapacity {
+        WatcherCapacity {
+            max_active: self.max_active_watchers.load(Ordering::Relaxed),
+            active: self.watchers.lock().unwrap().len(),
+        }
+    }
+
+    // Lowering the cap below the active count only blocks new starts
+    pub fn set_max_active_watchers(&self, max_active: usize) -> Result<(), String> {
+        if max_active == 0 {
+            return Err("Max active watchers must be at least 1".to_string());
+        }
+        self.max_active_watchers.store(max_active, Ordering::Relaxed);
+        Ok(())
+    }
+
+    pub fn event_coalescing(&self) -> EventCoalescing {
+        self.events.settings.lock().unwrap().clone()
+    }
+
+    pub fn set_event_coalescing(&self, settings: EventCoalescing) -> Result<(), String> {
+        if settings.max_events_per_second == 0 {
+            return Err("Event rate threshold must be at least 1 per second".to_string());
+        }
+        if settings.batch_interval_ms == 0 || settings.batch_interval_ms > MAX_BATCH_INTERVAL_MS {
+            return Err(format!(
+                "Batch interval must be between 1 and {}ms",
+                MAX_BATCH_INTERVAL_MS
+            ));
+        }
+        *self.events.settings.lock().unwrap() = settings;
+        Ok(())
+    }
+
     pub fn get_configs(&self) -> Vec<HotFolderConfig> {
         self.configs.lock().unwrap().values().cloned().collect()
     }
 
+    // Every backend the active folders submit to, default first
+    pub fn backend_urls(&self) -> Vec<String> {
+        let mut urls = vec![DEFAULT_BACKEND_URL.to_string()];
+        for config in self.configs.lock().unwrap().values() {
+            if let Some(url) = &config.backend_url {
+                let url = url.trim_end_matches('/').to_string();
+                if !urls.contains(&url) {
+                    urls.push(url);
+                }
+            }
+        }
+        urls
+    }
+
     pub fn is_watching(&self, folder_id: &str) -> bool {
         self.watchers.lock().unwrap().contains_key(folder_id)
     }
+
+    pub fn get_recent_errors(&self) -> Vec<WatcherError> {
+        self.recent_errors.lock().unwrap().iter().cloned().collect()
+    }
+
+    // Called when the backend reports a job finished for one of our folders
+    pub fn record_result(&self, folder_id: &str, success: bool) {
+        let mut stats = self.stats.lock().unwrap();
+        let stats = stats.entry(folder_id.to_string()).or_default();
+        if success {
+            stats.completed += 1;
+        } else {
+            stats.failed += 1;
+        }
+    }
+
+    pub fn get_folder_stats(&self) -> HashMap<String, FolderStats> {
+        self.stats.lock().unwrap().clone()
+    }
+
+    pub fn hold_submissions(&self) {
+        self.hold.lock().unwrap().hold(HoldKind::Manual);
+    }
+
+    pub fn hold_for_quiet_hours(&self) {
+        self.hold.lock().unwrap().hold(HoldKind::Scheduled);
+    }
+
+    pub fn submissions_held(&self) -> bool {
+        self.hold.lock().unwrap().active()
+    }
+
+    pub async fn release_submissions(&self) -> usize {
+        self.release(HoldKind::Manual).await
+    }
+
+    pub async fn release_for_quiet_hours(&self) -> usize {
+        self.release(HoldKind::Scheduled).await
+    }
+
+    // Lifts one kind of hold and, once nothing else holds, submits everything
+    // buffered in arrival order, including files that arrive during the flush
+    async fn release(&self, kind: HoldKind) -> usize {
+        if !self.hold.lock().unwrap().release(kind) {
+            return 0;
+        }
+
+        let mut guard = FlushGuard {
+            hold: &self.hold,
+            finished: false,
+        };
+        let mut flushed = 0;
+        loop {
+            let next = self.hold.lock().unwrap().next_to_flush();
+            let Some(held) = next else {
+                break;
+            };
+            held.submitter.submit(&held.path, &held.folder_id).await;
+            flushed += 1;
+        }
+        guard.finished = true;
+        flushed
+    }
+
+    async fn submit_unless_held(
+        &self,
+        submission: HeldSubmission,
+        throttle: Option<&mut LoadThrottle>,
+    ) -> Option<bool> {
+        submit_unless_held(&self.hold, submission, throttle, &self.app_handle).await
+    }
+
+    fn emit_file_added(&self, folder_id: &str, path: &str) {
+        self.events.emit(WatcherEvent {
+            event_type: "file_added".to_string(),
+            path: path.to_string(),
+            folder_id: folder_id.to_string(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+        });
+    }
+
+    // Files from non-filesystem sources go through the same hold, throttle and
+    // queue path as a watched folder's files, and are reported to the frontend
+    // the same way. True once the file is accepted, whether submitted now or
+    // buffered until submissions resume.
+    pub async fn submit_external(
+        &self,
+        source_id: &str,
+        path: &str,
+        throttle: Option<&mut LoadThrottle>,
+    ) -> bool {
+        let submission = HeldSubmission {
+            path: path.to_string(),
+            folder_id: source_id.to_string(),
+            submitter: self.submitter(DEFAULT_BACKEND_URL, "normal"),
+        };
+        let accepted = self.submit_unless_held(submission, throttle).await != Some(false);
+        if accepted {
+            self.emit_file_added(source_id, path);
+        }
+        accepted
+    }
+
+    pub fn held_submission_count(&self) -> usize {
+        self.hold.lock().unwrap().buffered.len()
+    }
+}
+
+// Used for dropped folders, which arrive without an extension list
+const DEFAULT_EXTENSIONS: [&str; 7] = ["jpg", "jpeg", "png", "webp", "bmp", "tiff", "tif"];
+const DEFAULT_STABILITY_TIMEOUT_MS: u64 = 2000;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IngestMode {
+    OneShotBatch,
+    Watch,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IngestSummary {
+    pub mode: IngestMode,
+    pub folder_id: String,
+    pub files_found: usize,
+    pub files_submitted: usize,
+    // Buffered while submissions are on hold; they go out on release
+    pub files_held: usize,
+    pub action: String,
+}
+
+fn collect_images(dir: &Path, files: &mut Vec<String>) -> Result<(), String> {
+    let entries = std::fs::read_dir(dir)
+        .map_err(|e| format!("Failed to read folder {}: {}", dir.display(), e))?;
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        // file_type doesn't follow symlinks, so a link back up the tree can't
+        // send the walk round in circles
+        if entry.file_type().is_ok_and(|file_type| file_type.is_dir()) {
+            collect_images(&path, files)?;
+        } else if let Some(ext) = path.extension() {
+            let ext = ext.to_string_lossy().to_lowercase();
+            if DEFAULT_EXTENSIONS.contains(&ext.as_str()) {
+                files.push(path.to_string_lossy().to_string());
+            }
+        }
+    }
+    Ok(())
+}
+
+fn normalize_extension(extension: &str) -> String {
+    extension.trim().trim_start_matches('.').to_lowercase()
+}
+
+fn unsupported_extensions(extensions: &[String], supported: &[String]) -> Vec<String> {
+    let supported: Vec<String> = supported.iter().map(|ext| normalize_extension(ext)).collect();
+    extensions
+        .iter()
+        .filter(|ext| !supported.contains(&normalize_extension(ext)))
+        .cloned()
+        .collect()
+}
+
+async fn check_extensions(config: &HotFolderConfig) -> Result<Vec<String>, String> {
+    if config.skip_format_check || config.extensions.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let formats = match fetch_supported_formats(&backend_for(config)?).await {
+        Ok(formats) => formats,
+        // Don't block watching just because the backend is down, unless asked to
+        Err(e) if config.strict_format_check => {
+            return Err(format!("Could not verify supported formats: {}", e))
+        }
+        Err(_) => return Ok(Vec::new()),
+    };
+
+    check_against_formats(config, &formats.input)
+}
+
+fn check_against_formats(
+    config: &HotFolderConfig,
+    supported: &[String],
+) -> Result<Vec<String>, String> {
+    let unsupported = unsupported_extensions(&config.extensions, supported);
+    if config.strict_format_check && !unsupported.is_empty() {
+        return Err(format!(
+            "Unsupported extensions for this backend: {}",
+            unsupported.join(", ")
+        ));
+    }
+    Ok(unsupported)
+}
+
+fn record_error(recent_errors: &Mutex<VecDeque<WatcherError>>, folder_id: &str, message: String) {
+    errors::record_last_error(errors::HOT_FOLDER, &message);
+
+    let mut recent_errors = recent_errors.lock().unwrap();
+    if recent_errors.len() >= MAX_RECENT_ERRORS {
+        recent_errors.pop_front();
+    }
+    recent_errors.push_back(WatcherError {
+        folder_id: folder_id.to_string(),
+        message,
+        timestamp: chrono::Utc::now().to_rfc3339(),
+    });
 }
 
 // Tauri commands
@@ -141,8 +1255,10 @@ impl HotFolderManager {
 pub async fn start_hot_folder(
     config: HotFolderConfig,
     manager: State<'_, Arc<HotFolderManager>>,
-) -> Result<(), String> {
-    manager.start_watching(config)
+) -> Result<Vec<String>, String> {
+    let unsupported = check_extensions(&config).await?;
+    manager.start_watching(config)?;
+    Ok(unsupported)
 }
 
 #[tauri::command]
@@ -166,4 +1282,639 @@ pub async fn is_folder_watching(
     manager: State<'_, Arc<HotFolderManager>>,
 ) -> Result<bool, String> {
     Ok(manager.is_watching(&folder_id))
-}
\ No newline at end of file
+}
+
+#[tauri::command]
+pub async fn get_folder_stats(
+    manager: State<'_, Arc<HotFolderManager>>,
+) -> Result<HashMap<String, FolderStats>, String> {
+    Ok(manager.get_folder_stats())
+}
+
+#[tauri::command]
+pub async fn get_watcher_capacity(
+    manager: State<'_, Arc<HotFolderManager>>,
+) -> Result<WatcherCapacity, String> {
+    Ok(manager.watcher_capacity())
+}
+
+#[tauri::command]
+pub async fn set_max_active_watchers(
+    max_active: usize,
+    manager: State<'_, Arc<HotFolderManager>>,
+) -> Result<(), String> {
+    manager.set_max_active_watchers(max_active)
+}
+
+#[tauri::command]
+pub async fn get_event_coalescing(
+    manager: State<'_, Arc<HotFolderManager>>,
+) -> Result<EventCoalescing, String> {
+    Ok(manager.event_coalescing())
+}
+
+#[tauri::command]
+pub async fn set_event_coalescing(
+    settings: EventCoalescing,
+    manager: State<'_, Arc<HotFolderManager>>,
+) -> Result<(), String> {
+    manager.set_event_coalescing(settings)
+}
+
+#[tauri::command]
+pub async fn hold_backend_submissions(
+    manager: State<'_, Arc<HotFolderManager>>,
+) -> Result<(), String> {
+    manager.hold_submissions();
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn release_backend_submissions(
+    manager: State<'_, Arc<HotFolderManager>>,
+) -> Result<usize, String> {
+    Ok(manager.release_submissions().await)
+}
+
+#[tauri::command]
+pub async fn get_held_submission_count(
+    manager: State<'_, Arc<HotFolderManager>>,
+) -> Result<usize, String> {
+    Ok(manager.held_submission_count())
+}
+
+#[tauri::command]
+pub async fn ingest_dropped_folder(
+    path: String,
+    mode: IngestMode,
+    cpu_throttle_threshold: Option<f32>,
+    manager: State<'_, Arc<HotFolderManager>>,
+) -> Result<IngestSummary, String> {
+    let folder = Path::new(&path);
+    if !folder.is_dir() {
+        return Err("Dropped path is not a folder".to_string());
+    }
+    validate_throttle_threshold(cpu_throttle_threshold)?;
+
+    let folder_id = format!("dropped-{}", chrono::Utc::now().timestamp_millis());
+    let mut files = Vec::new();
+    collect_images(folder, &mut files)?;
+    files.sort();
+
+    match mode {
+        IngestMode::OneShotBatch => {
+            // Outputs are assumed to be roughly the size of their inputs
+            let estimated_bytes = files
+                .iter()
+                .filter_map(|file| std::fs::metadata(file).ok())
+                .map(|metadata| metadata.len())
+                .sum();
+            ensure_backend_storage(estimated_bytes).await?;
+
+            // Same hold, quiet-hours and throttle path as a watched folder's files
+            let submitter = manager.submitter(DEFAULT_BACKEND_URL, "normal");
+            let mut throttle = cpu_throttle_threshold.map(LoadThrottle::new);
+            let (mut submitted, mut held) = (0, 0);
+            for file in &files {
+                manager.emit_file_added(&folder_id, file);
+                let submission = HeldSubmission {
+                    path: file.clone(),
+                    folder_id: folder_id.clone(),
+                    submitter: submitter.clone(),
+                };
+                match manager.submit_unless_held(submission, throttle.as_mut()).await {
+                    Some(true) => submitted += 1,
+                    Some(false) => {}
+                    None => held += 1,
+                }
+            }
+
+            let action = if held > 0 {
+                "Submitted current files; held files go out when submissions resume"
+            } else {
+                "Submitted current files for processing"
+            };
+            Ok(IngestSummary {
+                mode,
+                folder_id,
+                files_found: files.len(),
+                files_submitted: submitted,
+                files_held: held,
+                action: action.to_string(),
+            })
+        }
+        IngestMode::Watch => {
+            manager.start_watching(HotFolderConfig {
+                id: folder_id.clone(),
+                path,
+                enabled: true,
+                extensions: DEFAULT_EXTENSIONS.iter().map(|ext| ext.to_string()).collect(),
+                stability_timeout: DEFAULT_STABILITY_TIMEOUT_MS,
+                cpu_throttle_threshold,
+                burst_detection: None,
+                create_timeout: None,
+                modify_timeout: None,
+                skip_format_check: false,
+                strict_format_check: false,
+                backend_url: None,
+                tether_mode: false,
+                tether_temp_patterns: Vec::new(),
+            })?;
+
+            Ok(IngestSummary {
+                mode,
+                folder_id,
+                files_found: files.len(),
+                files_submitted: 0,
+                files_held: 0,
+                action: "Started watching as a hot folder".to_string(),
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn watcher_event(path: &str) -> WatcherEvent {
+        WatcherEvent {
+            event_type: "file_added".to_string(),
+            path: path.to_string(),
+            folder_id: "folder-1".to_string(),
+            timestamp: String::new(),
+        }
+    }
+
+    #[test]
+    fn event_burst_is_batched_in_order_after_the_rate_limit() {
+        let mut state = CoalesceState::default();
+        let start = Instant::now();
+        let deliveries: Vec<Delivery> = (0..6)
+            .map(|i| state.accept(watcher_event(&format!("/in/{}.jpg", i)), start, 3))
+            .collect();
+
+        assert!(matches!(&deliveries[0], Delivery::Now(event) if event.path == "/in/0.jpg"));
+        assert!(matches!(&deliveries[2], Delivery::Now(event) if event.path == "/in/2.jpg"));
+        assert!(matches!(deliveries[3], Delivery::Pending { start_flush: true }));
+        assert!(matches!(deliveries[5], Delivery::Pending { start_flush: false }));
+
+        // A slow event still waits behind the pending batch
+        let later = start + Duration::from_secs(2);
+        let delivery = state.accept(watcher_event("/in/6.jpg"), later, 3);
+        assert!(matches!(delivery, Delivery::Pending { start_flush: false }));
+        let batch: Vec<String> = std::mem::take(&mut state.pending)
+            .into_iter()
+            .map(|event| event.path)
+            .collect();
+        assert_eq!(batch, ["/in/3.jpg", "/in/4.jpg", "/in/5.jpg", "/in/6.jpg"]);
+
+        // Back to single events once flushed
+        let delivery = state.accept(watcher_event("/in/7.jpg"), later, 3);
+        assert!(matches!(delivery, Delivery::Now(event) if event.path == "/in/7.jpg"));
+    }
+
+    fn config(extensions: &[&str]) -> HotFolderConfig {
+        HotFolderConfig {
+            id: "folder".to_string(),
+            path: "/tmp/hot".to_string(),
+            enabled: true,
+            extensions: extensions.iter().map(|ext| ext.to_string()).collect(),
+            stability_timeout: DEFAULT_STABILITY_TIMEOUT_MS,
+            cpu_throttle_threshold: None,
+            burst_detection: None,
+            create_timeout: None,
+            modify_timeout: None,
+            skip_format_check: false,
+            strict_format_check: false,
+            backend_url: None,
+            tether_mode: false,
+            tether_temp_patterns: Vec::new(),
+        }
+    }
+
+    fn strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    fn submitter() -> Submitter {
+        Submitter {
+            backend_url: DEFAULT_BACKEND_URL.to_string(),
+            priority: "normal",
+            client: reqwest::Client::new(),
+            errors: Arc::default(),
+            stats: Arc::default(),
+        }
+    }
+
+    fn held(path: &str) -> HeldSubmission {
+        HeldSubmission {
+            path: path.to_string(),
+            folder_id: "folder".to_string(),
+            submitter: submitter(),
+        }
+    }
+
+    #[test]
+    fn submissions_carry_the_folder_id() {
+        let item = submitter().queue_item("/hot/a.jpg", "studio-a");
+        assert_eq!(item["folder_id"], "studio-a");
+        assert_eq!(item["path"], "/hot/a.jpg");
+    }
+
+    fn flush_order(hold: &mut SubmissionHold) -> Vec<String> {
+        std::iter::from_fn(|| hold.next_to_flush()).map(|held| held.path).collect()
+    }
+
+    #[test]
+    fn held_submissions_flush_before_new_arrivals() {
+        let mut hold = SubmissionHold::default();
+        assert!(hold.buffer(held("a.jpg")).is_some());
+
+        hold.hold(HoldKind::Manual);
+        assert!(hold.buffer(held("b.jpg")).is_none());
+        assert!(hold.buffer(held("c.jpg")).is_none());
+
+        // Arrivals during the flush queue behind what was held
+        assert!(hold.release(HoldKind::Manual));
+        assert!(!hold.release(HoldKind::Manual));
+        assert!(hold.buffer(held("d.jpg")).is_none());
+        assert_eq!(hold.next_to_flush().unwrap().path, "b.jpg");
+        assert!(hold.buffer(held("e.jpg")).is_none());
+        assert_eq!(flush_order(&mut hold), ["c.jpg", "d.jpg", "e.jpg"]);
+
+        assert!(!hold.flushing);
+        assert!(hold.buffer(held("f.jpg")).is_some());
+    }
+
+    #[test]
+    fn new_hold_stops_a_running_flush() {
+        let mut hold = SubmissionHold::default();
+        hold.hold(HoldKind::Manual);
+        hold.buffer(held("a.jpg"));
+        hold.buffer(held("b.jpg"));
+
+        assert!(hold.release(HoldKind::Manual));
+        assert_eq!(hold.next_to_flush().unwrap().path, "a.jpg");
+        hold.hold(HoldKind::Scheduled);
+        assert!(hold.next_to_flush().is_none());
+        assert_eq!(hold.buffered.len(), 1);
+
+        assert!(hold.release(HoldKind::Scheduled));
+        assert_eq!(flush_order(&mut hold), ["b.jpg"]);
+    }
+
+    #[test]
+    fn dropped_release_ends_the_flush() {
+        let hold = Mutex::new(SubmissionHold::default());
+        hold.lock().unwrap().hold(HoldKind::Scheduled);
+        hold.lock().unwrap().buffer(held("a.jpg"));
+        hold.lock().unwrap().buffer(held("b.jpg"));
+        assert!(hold.lock().unwrap().release(HoldKind::Scheduled));
+
+        {
+            let _guard = FlushGuard {
+                hold: &hold,
+                finished: false,
+            };
+            hold.lock().unwrap().next_to_flush();
+            // The release is dropped here, mid-flush
+        }
+
+        let mut hold = hold.into_inner().unwrap();
+        assert!(!hold.flushing);
+        assert!(hold.buffer(held("c.jpg")).is_some());
+        hold.hold(HoldKind::Manual);
+        hold.buffer(held("c.jpg"));
+        assert!(hold.release(HoldKind::Manual));
+        assert_eq!(flush_order(&mut hold), ["b.jpg", "c.jpg"]);
+    }
+
+    #[test]
+    fn quiet_hours_ending_keeps_a_manual_hold() {
+        let mut hold = SubmissionHold::default();
+        hold.hold(HoldKind::Manual);
+        hold.hold(HoldKind::Scheduled);
+        hold.buffer(held("a.jpg"));
+
+        // The window ends, but the user's hold is still in place
+        assert!(!hold.release(HoldKind::Scheduled));
+        assert!(hold.active());
+        assert!(hold.buffer(held("b.jpg")).is_none());
+        assert_eq!(hold.buffered.len(), 2);
+
+        assert!(hold.release(HoldKind::Manual));
+        assert_eq!(flush_order(&mut hold), ["a.jpg", "b.jpg"]);
+    }
+
+    #[test]
+    fn manual_release_during_quiet_hours_keeps_buffering() {
+        let mut hold = SubmissionHold::default();
+        hold.hold(HoldKind::Scheduled);
+        hold.hold(HoldKind::Manual);
+        hold.buffer(held("a.jpg"));
+
+        assert!(!hold.release(HoldKind::Manual));
+        assert!(hold.buffer(held("b.jpg")).is_none());
+
+        assert!(hold.release(HoldKind::Scheduled));
+        assert_eq!(flush_order(&mut hold), ["a.jpg", "b.jpg"]);
+    }
+
+    #[test]
+    fn tether_temp_file_is_picked_up_after_rename() {
+        let patterns = strings(&["*.cr3~"]);
+        let temp = PathBuf::from("/hot/IMG_0042.CR3.TMP");
+        let fin = PathBuf::from("/hot/IMG_0042.CR3");
+
+        // The capture software writes the temp name first...
+        assert_eq!(event_path(std::slice::from_ref(&temp), Some(&patterns)), None);
+        assert_eq!(event_path(&[PathBuf::from("/hot/~IMG.jpg")], Some(&patterns)), None);
+        assert_eq!(event_path(&[PathBuf::from("/hot/a.CR3~")], Some(&patterns)), None);
+        // ...then renames it to the final name, which is what gets submitted
+        let rename = [temp.clone(), fin.clone()];
+        assert_eq!(event_path(&rename, Some(&patterns)), Some(fin.as_path()));
+
+        // Outside tether mode the first path is used as before
+        assert_eq!(event_path(&rename, None), Some(temp.as_path()));
+    }
+
+    #[test]
+    fn temp_patterns_match_with_a_single_wildcard() {
+        assert!(matches_pattern("img.tmp", "*.tmp"));
+        assert!(matches_pattern("~img.jpg", "~*"));
+        assert!(matches_pattern("capture", "capture"));
+        assert!(!matches_pattern("img.jpg", "*.tmp"));
+        // Prefix and suffix can't overlap
+        assert!(!matches_pattern("a", "a*a"));
+    }
+
+    #[test]
+    fn watcher_past_the_cap_is_rejected() {
+        let mut watchers = HashMap::new();
+        for id in ["a", "b", "c"] {
+            check_watcher_slot(&watchers, id, 3).unwrap();
+            watchers.insert(id.to_string(), ());
+        }
+
+        assert_eq!(
+            check_watcher_slot(&watchers, "d", 3).unwrap_err(),
+            "Too many active watchers (limit 3)"
+        );
+        // Restarts keep their slot, and a raised cap makes room
+        assert!(check_watcher_slot(&watchers, "b", 3).is_ok());
+        assert!(check_watcher_slot(&watchers, "d", 4).is_ok());
+    }
+
+    #[test]
+    fn folders_route_to_their_own_backend() {
+        assert_eq!(backend_for(&config(&["jpg"])).unwrap(), DEFAULT_BACKEND_URL);
+
+        let remote = HotFolderConfig {
+            backend_url: Some("http://render-02:8888/".to_string()),
+            ..config(&["jpg"])
+        };
+        assert_eq!(backend_for(&remote).unwrap(), "http://render-02:8888");
+
+        let invalid = HotFolderConfig {
+            backend_url: Some("ftp://render-02".to_string()),
+            ..config(&["jpg"])
+        };
+        assert_eq!(
+            backend_for(&invalid).unwrap_err(),
+            "Backend URL must use http or https"
+        );
+    }
+
+    #[test]
+    fn unsupported_extensions_ignore_case_and_dots() {
+        let supported = strings(&["jpg", ".PNG", "tif"]);
+        let extensions = strings(&[".JPG", "png", "cr3", ".heic"]);
+        assert_eq!(unsupported_extensions(&extensions, &supported), ["cr3", ".heic"]);
+    }
+
+    #[test]
+    fn unsupported_extension_is_reported_or_rejected_when_strict() {
+        let supported = strings(&["jpg", "png"]);
+
+        let lenient = config(&["jpg", "cr3"]);
+        assert_eq!(check_against_formats(&lenient, &supported).unwrap(), ["cr3"]);
+
+        let strict = HotFolderConfig {
+            strict_format_check: true,
+            ..config(&["jpg", "cr3"])
+        };
+        assert_eq!(
+            check_against_formats(&strict, &supported).unwrap_err(),
+            "Unsupported extensions for this backend: cr3"
+        );
+        assert!(check_against_formats(&config(&["jpg"]), &supported).unwrap().is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn dropped_folder_walk_skips_symlinked_directories() {
+        let root = std::env::temp_dir().join(format!(
+            "nstant-collect-test-{}",
+            chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        let nested = root.join("nested");
+        std::fs::create_dir_all(&nested).unwrap();
+        std::fs::write(root.join("a.JPG"), b"").unwrap();
+        std::fs::write(nested.join("b.png"), b"").unwrap();
+        std::fs::write(nested.join("notes.txt"), b"").unwrap();
+        // A link back up the tree would recurse forever if followed
+        std::os::unix::fs::symlink(&root, nested.join("loop")).unwrap();
+
+        let mut files = Vec::new();
+        let collected = collect_images(&root, &mut files);
+        std::fs::remove_dir_all(&root).unwrap();
+
+        collected.unwrap();
+        files.sort();
+        assert_eq!(
+            files,
+            [
+                root.join("a.JPG").to_string_lossy(),
+                nested.join("b.png").to_string_lossy(),
+            ]
+        );
+    }
+
+    #[test]
+    fn sequence_numbers_split_off_the_trailing_digits() {
+        assert_eq!(sequence_number("/shoot/IMG_0042.CR3"), Some(("IMG_".to_string(), 42)));
+        // Zero padding doesn't change the number, so 0099 -> 0100 is consecutive
+        assert_eq!(sequence_number("DSC0099.jpg"), Some(("DSC".to_string(), 99)));
+        assert_eq!(sequence_number("DSC0100.jpg"), Some(("DSC".to_string(), 100)));
+        assert_eq!(sequence_number("0007.jpg"), Some((String::new(), 7)));
+        assert_eq!(sequence_number("portrait.jpg"), None);
+        assert_eq!(sequence_number("IMG_42_edit.jpg"), None);
+    }
+
+    fn bursts(window_ms: u64, max_group_size: usize) -> BurstTracker {
+        BurstTracker::new(BurstConfig {
+            window_ms,
+            max_group_size,
+            require_sequential_names: true,
+            submit_as_batch: false,
+        })
+    }
+
+    #[test]
+    fn consecutive_frames_group_until_the_sequence_breaks() {
+        let mut tracker = bursts(1000, 10);
+        let start = Instant::now();
+        let at = |ms| start + Duration::from_millis(ms);
+
+        assert!(tracker.push("DSC0099.jpg", at(0)).is_empty());
+        assert!(tracker.push("DSC0100.jpg", at(100)).is_empty());
+        // A gap in the numbering closes the burst and starts a new one
+        let closed = tracker.push("DSC0102.jpg", at(200));
+        assert_eq!(closed, [strings(&["DSC0099.jpg", "DSC0100.jpg"])]);
+        // So does a name without a number
+        assert_eq!(tracker.push("portrait.jpg", at(300)), [strings(&["DSC0102.jpg"])]);
+        assert_eq!(tracker.take(), ["portrait.jpg"]);
+    }
+
+    #[test]
+    fn interleaved_prefixes_are_separate_bursts() {
+        let mut tracker = bursts(1000, 10);
+        let start = Instant::now();
+
+        tracker.push("A_0001.jpg", start);
+        let closed = tracker.push("B_0002.jpg", start + Duration::from_millis(10));
+        assert_eq!(closed, [strings(&["A_0001.jpg"])]);
+        let closed = tracker.push("A_0002.jpg", start + Duration::from_millis(20));
+        assert_eq!(closed, [strings(&["B_0002.jpg"])]);
+    }
+
+    #[test]
+    fn burst_closes_once_the_window_passes() {
+        let mut tracker = bursts(500, 10);
+        let start = Instant::now();
+
+        tracker.push("IMG_0001.jpg", start);
+        // Arriving exactly at the deadline still counts
+        assert!(tracker.push("IMG_0002.jpg", start + Duration::from_millis(500)).is_empty());
+        assert_eq!(tracker.deadline(), Some(start + Duration::from_millis(1000)));
+
+        let closed = tracker.push("IMG_0003.jpg", start + Duration::from_millis(1001));
+        assert_eq!(closed, [strings(&["IMG_0001.jpg", "IMG_0002.jpg"])]);
+    }
+
+    #[test]
+    fn steady_arrivals_cannot_hold_a_burst_open_forever() {
+        let mut tracker = bursts(1000, 1000);
+        let start = Instant::now();
+
+        let mut closed = Vec::new();
+        for frame in 0..40u64 {
+            let path = format!("IMG_{:04}.jpg", frame);
+            closed.extend(tracker.push(&path, start + Duration::from_millis(frame * 900)));
+        }
+        // Each arrival is inside the window, but the span cap still splits them
+        assert_eq!(closed.len(), 1);
+        assert_eq!(closed[0].len(), 34);
+        assert_eq!(closed[0].last().unwrap(), "IMG_0033.jpg");
+        assert_eq!(tracker.take().len(), 6);
+    }
+
+    #[test]
+    fn full_group_closes_immediately() {
+        let mut tracker = bursts(1000, 2);
+        let start = Instant::now();
+
+        assert!(tracker.push("IMG_0001.jpg", start).is_empty());
+        assert_eq!(
+            tracker.push("IMG_0002.jpg", start),
+            [strings(&["IMG_0001.jpg", "IMG_0002.jpg"])]
+        );
+        assert_eq!(tracker.deadline(), None);
+    }
+
+    #[test]
+    fn unset_kind_timeouts_fall_back_to_the_stability_timeout() {
+        let (debounce, delays) = EventDelays::from_config(&config(&["jpg"]));
+        assert_eq!(debounce, Duration::from_millis(DEFAULT_STABILITY_TIMEOUT_MS));
+        let create = EventKind::Create(notify::event::CreateKind::File);
+        let modify = EventKind::Modify(notify::event::ModifyKind::Any);
+        assert_eq!(delays.for_kind(&create), Duration::ZERO);
+        assert_eq!(delays.for_kind(&modify), Duration::ZERO);
+
+        // The debouncer runs at the shortest timeout; longer kinds wait the rest
+        let (debounce, delays) = EventDelays::from_config(&HotFolderConfig {
+            stability_timeout: 2000,
+            create_timeout: Some(5000),
+            modify_timeout: Some(500),
+            ..config(&["jpg"])
+        });
+        assert_eq!(debounce, Duration::from_millis(500));
+        assert_eq!(delays.for_kind(&create), Duration::from_millis(4500));
+        assert_eq!(delays.for_kind(&modify), Duration::ZERO);
+        assert_eq!(delays.for_kind(&EventKind::Any), Duration::from_millis(1500));
+    }
+
+    #[test]
+    fn later_event_of_another_kind_keeps_the_longer_hold() {
+        let start = Instant::now();
+        let mut held = HeldEvents::default();
+
+        // Created with a 5s hold, then modified with a 1s hold
+        held.hold("a.jpg", start + Duration::from_secs(5));
+        held.hold("a.jpg", start + Duration::from_secs(1));
+        held.hold("b.jpg", start + Duration::from_secs(2));
+
+        assert_eq!(held.next_due(), Some(start + Duration::from_secs(2)));
+        assert!(held.take_due(start + Duration::from_secs(1)).is_empty());
+        assert_eq!(held.take_due(start + Duration::from_secs(3)), ["b.jpg"]);
+        assert!(held.is_held("a.jpg"));
+        assert_eq!(held.take_due(start + Duration::from_secs(5)), ["a.jpg"]);
+        assert_eq!(held.next_due(), None);
+    }
+
+    #[test]
+    fn held_events_are_released_in_due_order() {
+        let start = Instant::now();
+        let mut held = HeldEvents::default();
+        held.hold("late.jpg", start + Duration::from_secs(3));
+        held.hold("early.jpg", start + Duration::from_secs(1));
+        held.hold("middle.jpg", start + Duration::from_secs(2));
+
+        assert_eq!(held.take_due(start + Duration::from_secs(2)), ["early.jpg", "middle.jpg"]);
+        assert_eq!(held.take_all(), ["late.jpg"]);
+    }
+
+    #[test]
+    fn throttle_backs_off_while_over_the_threshold_and_clears_under_it() {
+        let mut state = ThrottleState {
+            threshold: 80.0,
+            delay: None,
+        };
+
+        assert_eq!(state.next_delay(80.0), None);
+        assert_eq!(state.next_delay(95.0), Some((THROTTLE_BASE_DELAY, true)));
+        assert_eq!(state.next_delay(95.0), Some((THROTTLE_BASE_DELAY * 2, false)));
+        assert_eq!(state.next_delay(81.0), Some((THROTTLE_BASE_DELAY * 4, false)));
+
+        // Dropping to the threshold resets, so the next stretch starts over
+        assert_eq!(state.next_delay(79.0), None);
+        assert_eq!(state.delay, None);
+        assert_eq!(state.next_delay(90.0), Some((THROTTLE_BASE_DELAY, true)));
+    }
+
+    #[test]
+    fn throttle_delay_is_capped() {
+        let mut state = ThrottleState {
+            threshold: 50.0,
+            delay: None,
+        };
+        let delays: Vec<Duration> = (0..10)
+            .filter_map(|_| state.next_delay(100.0))
+            .map(|(delay, _)| delay)
+            .collect();
+        assert_eq!(delays.len(), 10);
+        assert!(delays.windows(2).all(|pair| pair[0] <= pair[1]));
+        assert_eq!(delays.last(), Some(&THROTTLE_MAX_DELAY));
+    }
+}
@@ -0,0 +1,263 @@
+use crate::errors;
+use crate::hot_folder::{validate_throttle_threshold, HotFolderManager, LoadThrottle};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Manager, State};
+use tokio::task::JoinHandle;
+
+const MIN_POLL_INTERVAL_MS: u64 = 1000;
+// Backoff after a failed poll, doubling up to the max until the source recovers
+const RECONNECT_BASE_DELAY: Duration = Duration::from_secs(1);
+const RECONNECT_MAX_DELAY: Duration = Duration::from_secs(60);
+
+fn default_poll_interval() -> u64 {
+    5000
+}
+
+// An HTTP endpoint handing out images to process. GET {url} returns pending
+// messages; each is acknowledged with POST {url}/{id}/ack once submitted, so
+// unacknowledged messages are redelivered by the source. Files go through the
+// hot-folder queue path with the backend's default processing; there are no
+// pipeline presets to pick per source, so pipeline_preset is rejected rather
+// than silently ignored.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PollingSourceConfig {
+    pub id: String,
+    pub url: String,
+    #[serde(default = "default_poll_interval")]
+    pub poll_interval_ms: u64,
+    #[serde(default, skip_serializing)]
+    pub pipeline_preset: Option<String>,
+    // Opt-in load throttle, as on hot folders
+    #[serde(default)]
+    pub cpu_throttle_threshold: Option<f32>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct SourceMessage {
+    id: String,
+    path: String,
+}
+
+fn ack_url(source_url: &str, message_id: &str) -> String {
+    format!("{}/{}/ack", source_url.trim_end_matches('/'), message_id)
+}
+
+fn validate_config(config: &PollingSourceConfig) -> Result<(), String> {
+    let url = reqwest::Url::parse(&config.url)
+        .map_err(|e| format!("Invalid ingest source URL: {}", e))?;
+    if url.scheme() != "http" && url.scheme() != "https" {
+        return Err("Ingest source URL must use http or https".to_string());
+    }
+    if config.poll_interval_ms < MIN_POLL_INTERVAL_MS {
+        return Err(format!("Poll interval must be at least {}ms", MIN_POLL_INTERVAL_MS));
+    }
+    if config.pipeline_preset.is_some() {
+        return Err("Pipeline presets are not supported for ingest sources".to_string());
+    }
+    validate_throttle_threshold(config.cpu_throttle_threshold)
+}
+
+async fn poll_once(
+    client: &reqwest::Client,
+    config: &PollingSourceConfig,
+    manager: &HotFolderManager,
+    mut throttle: Option<&mut LoadThrottle>,
+) -> Result<(), String> {
+    let response = client
+        .get(&config.url)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to connect to ingest source {}: {}", config.id, e))?;
+
+    if !response.status().is_success() {
+        return Err(format!("Ingest source {} returned {}", config.id, response.status()));
+    }
+
+    let messages = response
+        .json::<Vec<SourceMessage>>()
+        .await
+        .map_err(|e| format!("Invalid response from ingest source {}: {}", config.id, e))?;
+
+    for message in messages {
+        // Leave the rest unacknowledged; the source redelivers them on the next poll
+        if !manager.submit_external(&config.id, &message.path, throttle.as_deref_mut()).await {
+            return Err(format!("Failed to submit {} from {}", message.path, config.id));
+        }
+
+        let response = client
+            .post(ack_url(&config.url, &message.id))
+            .send()
+            .await
+            .map_err(|e| format!("Failed to acknowledge message {}: {}", message.id, e))?;
+        if !response.status().is_success() {
+            return Err(format!(
+                "Ingest source {} rejected acknowledgement of {}: {}",
+                config.id,
+                message.id,
+                response.status()
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+pub struct IngestSources {
+    sources: Mutex<HashMap<String, (PollingSourceConfig, JoinHandle<()>)>>,
+    app_handle: AppHandle,
+}
+
+impl IngestSources {
+    pub fn new(app_handle: AppHandle) -> Self {
+        Self {
+            sources: Mutex::new(HashMap::new()),
+            app_handle,
+        }
+    }
+
+    pub fn connect(&self, config: PollingSourceConfig) -> Result<(), String> {
+        validate_config(&config)?;
+
+        let app_handle = self.app_handle.clone();
+        let task_config = config.clone();
+        let task = tokio::spawn(async move {
+            let config = task_config;
+            let manager = app_handle.state::<Arc<HotFolderManager>>().inner().clone();
+            let client = reqwest::Client::new();
+            let interval = Duration::from_millis(config.poll_interval_ms);
+            let mut backoff = RECONNECT_BASE_DELAY;
+            let mut throttle = config.cpu_throttle_threshold.map(LoadThrottle::new);
+
+            loop {
+                // Leave messages with the source while submissions are held
+                // rather than piling them into the hold buffer; any that race
+                // past this check are buffered by submit_external
+                if manager.submissions_held() {
+                    tokio::time::sleep(interval).await;
+                    continue;
+                }
+
+                match poll_once(&client, &config, &manager, throttle.as_mut()).await {
+                    Ok(()) => {
+                        backoff = RECONNECT_BASE_DELAY;
+                        tokio::time::sleep(interval).await;
+                    }
+                    Err(e) => {
+                        errors::record_last_error(errors::INGEST_SOURCE, &e);
+                        tokio::time::sleep(backoff).await;
+                        backoff = (backoff * 2).min(RECONNECT_MAX_DELAY);
+                    }
+                }
+            }
+        });
+
+        let previous = self
+            .sources
+            .lock()
+            .unwrap()
+            .insert(config.id.clone(), (config, task));
+        if let Some((_, previous)) = previous {
+            previous.abort();
+        }
+        Ok(())
+    }
+
+    pub fn disconnect(&self, source_id: &str) -> Result<(), String> {
+        match self.sources.lock().unwrap().remove(source_id) {
+            Some((_, task)) => {
+                task.abort();
+                Ok(())
+            }
+            None => Err(format!("No ingest source '{}'", source_id)),
+        }
+    }
+
+    pub fn get_configs(&self) -> Vec<PollingSourceConfig> {
+        self.sources
+            .lock()
+            .unwrap()
+            .values()
+            .map(|(config, _)| config.clone())
+            .collect()
+    }
+}
+
+// Tauri commands
+#[tauri::command]
+pub async fn connect_polling_source(
+    config: PollingSourceConfig,
+    sources: State<'_, Arc<IngestSources>>,
+) -> Result<(), String> {
+    sources.connect(config)
+}
+
+#[tauri::command]
+pub async fn disconnect_polling_source(
+    source_id: String,
+    sources: State<'_, Arc<IngestSources>>,
+) -> Result<(), String> {
+    sources.disconnect(&source_id)
+}
+
+#[tauri::command]
+pub async fn get_polling_sources(
+    sources: State<'_, Arc<IngestSources>>,
+) -> Result<Vec<PollingSourceConfig>, String> {
+    Ok(sources.get_configs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(url: &str) -> PollingSourceConfig {
+        serde_json::from_value(serde_json::json!({ "id": "dam", "url": url })).unwrap()
+    }
+
+    #[test]
+    fn source_config_is_validated() {
+        let source = config("https://dam.example.com/outbox/");
+        assert_eq!(source.poll_interval_ms, 5000);
+        assert!(validate_config(&source).is_ok());
+
+        assert!(validate_config(&config("ftp://dam.example.com")).is_err());
+        let fast = PollingSourceConfig {
+            poll_interval_ms: 10,
+            ..source.clone()
+        };
+        assert!(validate_config(&fast).is_err());
+
+        let preset: PollingSourceConfig = serde_json::from_value(serde_json::json!({
+            "id": "dam",
+            "url": "https://dam.example.com/outbox",
+            "pipeline_preset": "web-export",
+        }))
+        .unwrap();
+        assert_eq!(
+            validate_config(&preset).unwrap_err(),
+            "Pipeline presets are not supported for ingest sources"
+        );
+
+        let throttled = PollingSourceConfig {
+            cpu_throttle_threshold: Some(80.0),
+            ..source.clone()
+        };
+        assert!(validate_config(&throttled).is_ok());
+        let overloaded = PollingSourceConfig {
+            cpu_throttle_threshold: Some(150.0),
+            ..source
+        };
+        assert!(validate_config(&overloaded).is_err());
+    }
+
+    #[test]
+    fn messages_are_acknowledged_under_the_source_url() {
+        assert_eq!(
+            ack_url("https://dam.example.com/outbox/", "m-1"),
+            "https://dam.example.com/outbox/m-1/ack"
+        );
+    }
+}
@@ -1,18 +1,99 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+mod backend_events;
+mod batch;
 mod commands;
+mod comparison;
+mod contact_sheet;
+mod diagnostics;
+mod errors;
+mod fingerprint;
 mod hot_folder;
+mod ingest_source;
+mod metadata;
+mod pipeline;
+mod quiet_hours;
+mod smoke_test;
+mod throughput;
+mod tiling;
 
-use commands::{get_image_info, process_image, check_backend_health};
-use hot_folder::{start_hot_folder, stop_hot_folder, get_hot_folders, is_folder_watching, HotFolderManager};
+use backend_events::{start_backend_events, stop_backend_events, BackendEventStream};
+use batch::{detect_output_conflicts, execute_batch, retry_failed_batch};
+use commands::{
+    extract_palette, get_backend_disk_space, get_image_info, get_supported_formats, process_image,
+    check_backend_health, measure_color_accuracy, suggest_crops,
+};
+use comparison::generate_comparison_preview;
+use contact_sheet::generate_contact_sheet;
+use diagnostics::{export_diagnostics, generate_diagnostics};
+use errors::{clear_last_errors, get_last_errors};
+use fingerprint::{find_by_fingerprint, get_fingerprint};
+use hot_folder::{
+    get_folder_stats, get_held_submission_count, hold_backend_submissions, ingest_dropped_folder,
+    release_backend_submissions, start_hot_folder, stop_hot_folder, get_hot_folders,
+    is_folder_watching, get_watcher_capacity, set_max_active_watchers, get_event_coalescing,
+    set_event_coalescing, HotFolderManager,
+};
+use ingest_source::{
+    connect_polling_source, disconnect_polling_source, get_polling_sources, IngestSources,
+};
+use metadata::{apply_metadata_csv, embed_metadata, read_metadata};
+use pipeline::{
+    apply_geometry, auto_correct, encode_to_target_size, execute_pipeline, get_processor_model_info,
+    get_request, process_image_from_url, rerun_request, set_max_processing_time, verify_determinism,
+};
+use quiet_hours::{get_quiet_hours, set_quiet_hours, QuietHoursScheduler};
+use smoke_test::run_smoke_test;
 use std::sync::Arc;
+use throughput::{get_throughput, start_queue_eta, stop_queue_eta, QueueEtaMonitor};
+use tiling::execute_pipeline_tiled;
 use tauri::Manager;
 
+// Override with NSTANT_WORKER_THREADS (1-256); defaults to one worker per CPU core,
+// matching tokio's own default. Raise it on many-core machines running large batches.
+const WORKER_THREADS_ENV: &str = "NSTANT_WORKER_THREADS";
+const MAX_WORKER_THREADS: usize = 256;
+
+fn worker_threads() -> usize {
+    let default = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(4);
+
+    match std::env::var(WORKER_THREADS_ENV) {
+        Ok(value) => match value.trim().parse::<usize>() {
+            Ok(threads) if (1..=MAX_WORKER_THREADS).contains(&threads) => threads,
+            _ => {
+                eprintln!(
+                    "Ignoring invalid {}={:?}, using {} worker threads",
+                    WORKER_THREADS_ENV, value, default
+                );
+                default
+            }
+        },
+        Err(_) => default,
+    }
+}
+
 fn main() {
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(worker_threads())
+        .enable_all()
+        .build()
+        .expect("failed to build tokio runtime");
+    tauri::async_runtime::set(runtime.handle().clone());
+
     tauri::Builder::default()
         .setup(|app| {
             let hot_folder_manager = Arc::new(HotFolderManager::new(app.handle().clone()));
             app.manage(hot_folder_manager);
+            let backend_event_stream = Arc::new(BackendEventStream::new(app.handle().clone()));
+            app.manage(backend_event_stream);
+            let quiet_hours_scheduler = Arc::new(QuietHoursScheduler::new(app.handle().clone()));
+            app.manage(quiet_hours_scheduler);
+            let ingest_sources = Arc::new(IngestSources::new(app.handle().clone()));
+            app.manage(ingest_sources);
+            let queue_eta_monitor = Arc::new(QueueEtaMonitor::new(app.handle().clone()));
+            app.manage(queue_eta_monitor);
             Ok(())
         })
         .plugin(tauri_plugin_shell::init())
@@ -22,10 +103,60 @@ fn main() {
             get_image_info,
             process_image,
             check_backend_health,
+            get_supported_formats,
+            extract_palette,
+            get_backend_disk_space,
             start_hot_folder,
             stop_hot_folder,
             get_hot_folders,
-            is_folder_watching
+            is_folder_watching,
+            hold_backend_submissions,
+            release_backend_submissions,
+            get_held_submission_count,
+            ingest_dropped_folder,
+            get_folder_stats,
+            generate_diagnostics,
+            export_diagnostics,
+            auto_correct,
+            start_backend_events,
+            stop_backend_events,
+            process_image_from_url,
+            verify_determinism,
+            execute_pipeline,
+            encode_to_target_size,
+            set_max_processing_time,
+            apply_geometry,
+            get_request,
+            rerun_request,
+            embed_metadata,
+            read_metadata,
+            execute_pipeline_tiled,
+            detect_output_conflicts,
+            get_last_errors,
+            clear_last_errors,
+            get_fingerprint,
+            find_by_fingerprint,
+            get_watcher_capacity,
+            set_max_active_watchers,
+            generate_contact_sheet,
+            apply_metadata_csv,
+            set_quiet_hours,
+            get_quiet_hours,
+            get_processor_model_info,
+            run_smoke_test,
+            measure_color_accuracy,
+            connect_polling_source,
+            disconnect_polling_source,
+            get_polling_sources,
+            generate_comparison_preview,
+            execute_batch,
+            retry_failed_batch,
+            suggest_crops,
+            get_event_coalescing,
+            set_event_coalescing,
+            get_throughput,
+            start_queue_eta,
+            stop_queue_eta
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
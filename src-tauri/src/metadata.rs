@@ -0,0 +1,357 @@
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+// Columns accepted in a metadata CSV; "filename" is required, the rest optional
+const CSV_FILENAME_COLUMN: &str = "filename";
+const CSV_METADATA_COLUMNS: [&str; 4] = ["creator", "copyright", "keywords", "description"];
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ImageMetadata {
+    pub creator: Option<String>,
+    pub copyright: Option<String>,
+    #[serde(default)]
+    pub keywords: Vec<String>,
+    pub description: Option<String>,
+}
+
+impl ImageMetadata {
+    pub fn is_empty(&self) -> bool {
+        self.creator.is_none()
+            && self.copyright.is_none()
+            && self.keywords.is_empty()
+            && self.description.is_none()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetadataRowError {
+    pub row: usize, // 1-based, excluding the header
+    pub file_name: String,
+    pub error: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MetadataReport {
+    pub updated: Vec<String>,
+    // Rows whose filename has no matching image in the folder
+    pub unmatched: Vec<MetadataRowError>,
+    pub failed: Vec<MetadataRowError>,
+}
+
+#[derive(Debug, Serialize)]
+struct EmbedMetadataRequest<'a> {
+    path: &'a str,
+    metadata: &'a ImageMetadata,
+    iptc: bool,
+}
+
+// XMP is written for every format that can carry it; IPTC only where the
+// container has a standard place for it
+fn metadata_support(path: &Path) -> Result<bool, String> {
+    let extension = path
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+
+    match extension.as_str() {
+        "jpg" | "jpeg" | "tif" | "tiff" => Ok(true),
+        "png" | "webp" => Ok(false),
+        _ => Err(format!(
+            "Embedding metadata is not supported for '{}' files",
+            extension
+        )),
+    }
+}
+
+fn embed_request<'a>(
+    path: &'a str,
+    metadata: &'a ImageMetadata,
+) -> Result<EmbedMetadataRequest<'a>, String> {
+    if metadata.is_empty() {
+        return Err("No metadata fields given".to_string());
+    }
+    let iptc = metadata_support(Path::new(path))?;
+    Ok(EmbedMetadataRequest {
+        path,
+        metadata,
+        iptc,
+    })
+}
+
+pub async fn embed(path: &str, metadata: &ImageMetadata) -> Result<(), String> {
+    if !Path::new(path).exists() {
+        return Err("File not found".to_string());
+    }
+    let request = embed_request(path, metadata)?;
+
+    // Call Python backend
+    let client = reqwest::Client::new();
+    let response = client
+        .post("http://localhost:8888/image/metadata")
+        .json(&request)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to connect to processing engine: {}", e))?;
+
+    if !response.status().is_success() {
+        let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+        return Err(format!("Processing engine error: {}", error_text));
+    }
+
+    Ok(())
+}
+
+// Reads back what embed wrote, from the file's XMP packet
+pub async fn read(path: &str) -> Result<ImageMetadata, String> {
+    if !Path::new(path).exists() {
+        return Err("File not found".to_string());
+    }
+
+    // Call Python backend
+    let client = reqwest::Client::new();
+    let response = client
+        .post("http://localhost:8888/image/metadata/read")
+        .json(&serde_json::json!({ "path": path }))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to connect to processing engine: {}", e))?;
+
+    if !response.status().is_success() {
+        let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+        return Err(format!("Processing engine error: {}", error_text));
+    }
+
+    response
+        .json::<ImageMetadata>()
+        .await
+        .map_err(|e| format!("Invalid response from processing engine: {}", e))
+}
+
+// Spreadsheet exports are usually UTF-8 (often with a BOM); fall back to
+// Latin-1 for legacy exports rather than rejecting the file
+fn decode_csv(bytes: &[u8]) -> String {
+    let bytes = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(bytes);
+    match std::str::from_utf8(bytes) {
+        Ok(text) => text.to_string(),
+        Err(_) => bytes.iter().map(|&byte| byte as char).collect(),
+    }
+}
+
+fn non_empty(value: Option<&str>) -> Option<String> {
+    value
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
+// Parses rows of (row number, filename, metadata); keywords are ';'-separated
+fn parse_metadata_csv(text: &str) -> Result<Vec<(usize, String, ImageMetadata)>, String> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_reader(text.as_bytes());
+
+    let headers: Vec<String> = reader
+        .headers()
+        .map_err(|e| format!("Failed to read CSV header: {}", e))?
+        .iter()
+        .map(|header| header.to_lowercase())
+        .collect();
+
+    let unknown: Vec<&str> = headers
+        .iter()
+        .map(String::as_str)
+        .filter(|header| *header != CSV_FILENAME_COLUMN && !CSV_METADATA_COLUMNS.contains(header))
+        .collect();
+    if !unknown.is_empty() {
+        return Err(format!(
+            "Unsupported CSV columns: {} (expected {} and any of {})",
+            unknown.join(", "),
+            CSV_FILENAME_COLUMN,
+            CSV_METADATA_COLUMNS.join(", ")
+        ));
+    }
+    let column = |name: &str| headers.iter().position(|header| header == name);
+    let filename_column = column(CSV_FILENAME_COLUMN)
+        .ok_or_else(|| format!("CSV is missing the '{}' column", CSV_FILENAME_COLUMN))?;
+
+    let mut rows = Vec::new();
+    for (index, record) in reader.records().enumerate() {
+        let row = index + 1;
+        let record = record.map_err(|e| format!("Failed to parse CSV row {}: {}", row, e))?;
+        let field = |name: &str| non_empty(column(name).and_then(|i| record.get(i)));
+
+        let metadata = ImageMetadata {
+            creator: field("creator"),
+            copyright: field("copyright"),
+            keywords: field("keywords")
+                .map(|keywords| {
+                    keywords
+                        .split(';')
+                        .filter_map(|keyword| non_empty(Some(keyword)))
+                        .collect()
+                })
+                .unwrap_or_default(),
+            description: field("description"),
+        };
+        let file_name = record.get(filename_column).unwrap_or_default().to_string();
+        rows.push((row, file_name, metadata));
+    }
+
+    Ok(rows)
+}
+
+// Rows may only name files directly inside the image folder; separators,
+// "..", and absolute or drive-prefixed paths would reach outside it
+fn is_plain_file_name(file_name: &str) -> bool {
+    if file_name.contains(['/', '\\']) {
+        return false;
+    }
+    let mut components = Path::new(file_name).components();
+    matches!(
+        (components.next(), components.next()),
+        (None, None) | (Some(std::path::Component::Normal(_)), None)
+    )
+}
+
+// Tauri commands
+#[tauri::command]
+pub async fn embed_metadata(path: String, metadata: ImageMetadata) -> Result<(), String> {
+    embed(&path, &metadata).await
+}
+
+#[tauri::command]
+pub async fn read_metadata(path: String) -> Result<ImageMetadata, String> {
+    read(&path).await
+}
+
+#[tauri::command]
+pub async fn apply_metadata_csv(csv_path: String, image_dir: String) -> Result<MetadataReport, String> {
+    let bytes = tokio::fs::read(&csv_path)
+        .await
+        .map_err(|e| format!("Failed to read {}: {}", csv_path, e))?;
+    let rows = parse_metadata_csv(&decode_csv(&bytes))?;
+
+    let image_dir = Path::new(&image_dir);
+    if !image_dir.is_dir() {
+        return Err("Image folder not found".to_string());
+    }
+
+    let mut report = MetadataReport::default();
+    for (row, file_name, metadata) in rows {
+        if !is_plain_file_name(&file_name) {
+            report.failed.push(MetadataRowError {
+                row,
+                file_name,
+                error: "Filename must not contain a path".to_string(),
+            });
+            continue;
+        }
+
+        let image_path = image_dir.join(&file_name);
+        if file_name.is_empty() || !image_path.is_file() {
+            report.unmatched.push(MetadataRowError {
+                row,
+                file_name,
+                error: "No matching file".to_string(),
+            });
+            continue;
+        }
+
+        let image_path = image_path.to_string_lossy().to_string();
+        match embed(&image_path, &metadata).await {
+            Ok(()) => report.updated.push(image_path),
+            Err(error) => report.failed.push(MetadataRowError {
+                row,
+                file_name,
+                error,
+            }),
+        }
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wedding() -> ImageMetadata {
+        ImageMetadata {
+            creator: Some("Ana Núñez".to_string()),
+            copyright: Some("© 2026 Studio".to_string()),
+            keywords: vec!["wedding".to_string(), "outdoor".to_string()],
+            description: None,
+        }
+    }
+
+    #[test]
+    fn embed_requests_iptc_only_where_the_container_carries_it() {
+        let metadata = wedding();
+
+        let jpeg = serde_json::to_value(embed_request("photo.JPG", &metadata).unwrap()).unwrap();
+        assert_eq!(jpeg["path"], "photo.JPG");
+        assert_eq!(jpeg["iptc"], true);
+        assert_eq!(jpeg["metadata"]["creator"], "Ana Núñez");
+        assert_eq!(jpeg["metadata"]["keywords"], serde_json::json!(["wedding", "outdoor"]));
+        assert!(jpeg["metadata"]["description"].is_null());
+
+        let png = serde_json::to_value(embed_request("photo.png", &metadata).unwrap()).unwrap();
+        assert_eq!(png["iptc"], false);
+    }
+
+    #[test]
+    fn embed_rejects_empty_metadata_and_unsupported_files() {
+        assert_eq!(
+            embed_request("photo.jpg", &ImageMetadata::default()).unwrap_err(),
+            "No metadata fields given"
+        );
+        assert_eq!(
+            embed_request("photo.bmp", &wedding()).unwrap_err(),
+            "Embedding metadata is not supported for 'bmp' files"
+        );
+    }
+
+    #[test]
+    fn read_maps_a_file_without_metadata_to_empty() {
+        // What /image/metadata/read answers for a file with no XMP packet
+        let read: ImageMetadata = serde_json::from_str(
+            r#"{"creator": null, "copyright": null, "keywords": [], "description": null}"#,
+        )
+        .unwrap();
+        assert!(read.is_empty());
+
+        let partial: ImageMetadata = serde_json::from_str(r#"{"creator": "Ana Núñez"}"#).unwrap();
+        assert!(!partial.is_empty());
+        assert!(partial.keywords.is_empty());
+    }
+
+    #[test]
+    fn csv_filenames_stay_inside_the_image_folder() {
+        assert!(is_plain_file_name("IMG_0001.jpg"));
+        assert!(is_plain_file_name("wedding day.JPG"));
+        assert!(is_plain_file_name(""));
+
+        for name in [
+            "../IMG_0001.jpg",
+            "..",
+            ".",
+            "/etc/passwd",
+            "sub/IMG_0001.jpg",
+            "sub\\IMG_0001.jpg",
+            "C:\\photos\\IMG_0001.jpg",
+        ] {
+            assert!(!is_plain_file_name(name), "{} accepted", name);
+        }
+    }
+
+    #[test]
+    fn iptc_only_for_containers_that_carry_it() {
+        assert_eq!(metadata_support(Path::new("a.JPG")), Ok(true));
+        assert_eq!(metadata_support(Path::new("a.tiff")), Ok(true));
+        assert_eq!(metadata_support(Path::new("a.webp")), Ok(false));
+        assert_eq!(
+            metadata_support(Path::new("a.bmp")).unwrap_err(),
+            "Embedding metadata is not supported for 'bmp' files"
+        );
+    }
+}
@@ -0,0 +1,1203 @@
+use crate::commands::get_image_info;
+use crate::errors;
+use crate::metadata::{self, ImageMetadata};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use std::collections::VecDeque;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tokio::io::AsyncWriteExt;
+
+// Refuse remote images larger than this
+const MAX_DOWNLOAD_BYTES: u64 = 200 * 1024 * 1024;
+
+// Bounds for verify_determinism; each run is a full pipeline execution
+const MIN_DETERMINISM_RUNS: usize = 2;
+const MAX_DETERMINISM_RUNS: usize = 10;
+
+// Global per-job time limit in milliseconds; 0 means no limit
+static DEFAULT_MAX_PROCESSING_MS: AtomicU64 = AtomicU64::new(0);
+
+// Executed requests kept for get_request/rerun_request, oldest evicted first
+const MAX_REQUEST_HISTORY: usize = 200;
+static REQUEST_HISTORY: Mutex<VecDeque<(String, ProcessingRequest)>> = Mutex::new(VecDeque::new());
+
+// Quality search steps the backend may take for encode_to_target_size
+const MAX_ENCODE_ITERATIONS: u32 = 8;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProcessingNode {
+    pub id: String,
+    pub processor_type: String,
+    #[serde(default)]
+    pub parameters: serde_json::Value,
+}
+
+// Speed/quality dial forwarded to the backend, which maps it onto its own
+// per-node quality settings
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProcessingProfile {
+    Draft,
+    #[default]
+    Standard,
+    HighQuality,
+}
+
+// Output sharpening applied as the last node. Base settings at a 3000px long
+// edge, radius scaled with the output size (0.5x - 2x):
+//   screen        amount 0.35  radius 0.6  threshold 0
+//   web           amount 0.50  radius 0.5  threshold 0
+//   print_matte   amount 1.20  radius 1.2  threshold 2
+//   print_glossy  amount 0.90  radius 1.0  threshold 2
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OutputMedium {
+    #[default]
+    None,
+    Screen,
+    Web,
+    PrintMatte,
+    PrintGlossy,
+}
+
+const SHARPENING_REFERENCE_EDGE: f32 = 3000.0;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SharpeningSettings {
+    pub medium: OutputMedium,
+    pub amount: f32,
+    pub radius: f32,
+    pub threshold: u8,
+}
+
+impl OutputMedium {
+    fn sharpening(self, long_edge: u32) -> Option<SharpeningSettings> {
+        let (amount, radius, threshold) = match self {
+            OutputMedium::None => return None,
+            OutputMedium::Screen => (0.35, 0.6, 0),
+            OutputMedium::Web => (0.5, 0.5, 0),
+            OutputMedium::PrintMatte => (1.2, 1.2, 2),
+            OutputMedium::PrintGlossy => (0.9, 1.0, 2),
+        };
+        let scale = (long_edge as f32 / SHARPENING_REFERENCE_EDGE).clamp(0.5, 2.0);
+        Some(SharpeningSettings {
+            medium: self,
+            amount,
+            radius: radius * scale,
+            threshold,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProcessingRequest {
+    pub input_path: String,
+    pub output_path: String,
+    pub nodes: Vec<ProcessingNode>,
+    #[serde(default)]
+    pub profile: ProcessingProfile,
+    // Overrides the global default set via set_max_processing_time
+    #[serde(default)]
+    pub max_processing_ms: Option<u64>,
+    // Originating hot folder, echoed back in the result for attribution
+    #[serde(default)]
+    pub folder_id: Option<String>,
+    // Written into the output once processing succeeds
+    #[serde(default)]
+    pub embed_metadata: Option<ImageMetadata>,
+    #[serde(default)]
+    pub output_sharpening: OutputMedium,
+}
+
+impl ProcessingRequest {
+    pub fn new(input_path: String, output_path: String, nodes: Vec<ProcessingNode>) -> Self {
+        Self {
+            input_path,
+            output_path,
+            nodes,
+            profile: ProcessingProfile::default(),
+            max_processing_ms: None,
+            folder_id: None,
+            embed_metadata: None,
+            output_sharpening: OutputMedium::None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProcessingResult {
+    // Identifies the executed request in the replay history
+    #[serde(default)]
+    pub session_id: Option<String>,
+    pub success: bool,
+    pub input_path: String,
+    // Results built from several inputs (contact sheets) list them here and
+    // leave input_path empty
+    #[serde(default)]
+    pub input_paths: Vec<String>,
+    pub output_path: String,
+    pub operations_applied: u32,
+    pub processing_time_ms: u64,
+    pub profile: ProcessingProfile,
+    #[serde(default)]
+    pub folder_id: Option<String>,
+    #[serde(default)]
+    pub encoding: Option<EncodingOutcome>,
+    // Models used by ML-backed nodes, so outputs can be traced to exact weights
+    #[serde(default)]
+    pub models: Vec<NodeModelInfo>,
+    #[serde(default)]
+    pub output_sharpening: Option<SharpeningSettings>,
+    #[serde(default)]
+    pub warning: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EncodingOutcome {
+    pub format: String,
+    pub quality: u8,
+    pub size_bytes: u64,
+    pub max_bytes: u64,
+    pub target_met: bool,
+}
+
+impl EncodingOutcome {
+    fn new(format: String, quality: u8, size_bytes: u64, max_bytes: u64) -> Self {
+        Self {
+            format,
+            quality,
+            size_bytes,
+            max_bytes,
+            target_met: size_bytes <= max_bytes,
+        }
+    }
+
+    fn warning(&self) -> Option<String> {
+        (!self.target_met).then(|| {
+            format!(
+                "Smallest achievable size is {} bytes, above the {} byte target",
+                self.size_bytes, self.max_bytes
+            )
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelInfo {
+    pub name: String,
+    pub version: String,
+    pub hash: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeModelInfo {
+    pub node_id: String,
+    pub model: ModelInfo,
+}
+
+#[derive(Deserialize)]
+struct ModelInfoResponse {
+    // Absent for processors that don't wrap a model
+    #[serde(default)]
+    model: Option<ModelInfo>,
+}
+
+#[derive(Serialize)]
+struct ProcessRequest<'a> {
+    job_id: &'a str,
+    path: &'a str,
+    output_path: &'a str,
+    operations: &'a [ProcessingNode],
+    profile: ProcessingProfile,
+    folder_id: Option<&'a str>,
+}
+
+#[derive(Deserialize)]
+struct ProcessResponse {
+    status: String,
+    output_path: String,
+    operations_applied: u32,
+    #[serde(default)]
+    folder_id: Option<String>,
+    #[serde(default)]
+    models: Vec<NodeModelInfo>,
+}
+
+// The medium's settings depend on the output size, so look up the source
+// dimensions only when sharpening is requested
+async fn output_sharpening(request: &ProcessingRequest) -> Result<Option<SharpeningSettings>, String> {
+    if request.output_sharpening == OutputMedium::None {
+        return Ok(None);
+    }
+    let info = get_image_info(request.input_path.clone()).await?;
+    Ok(request.output_sharpening.sharpening(info.width.max(info.height)))
+}
+
+// The request as it actually runs: the effective time limit filled in and the
+// output sharpening resolved into a trailing node, so a rerun repeats it
+// exactly even after the global default or the source file changes
+fn effective_request(
+    request: &ProcessingRequest,
+    max_processing_ms: Option<u64>,
+    sharpening: Option<&SharpeningSettings>,
+) -> ProcessingRequest {
+    let mut effective = request.clone();
+    effective.max_processing_ms = max_processing_ms;
+    if let Some(settings) = sharpening {
+        effective.nodes.push(ProcessingNode {
+            id: "output_sharpening".to_string(),
+            processor_type: "sharpen".to_string(),
+            parameters: serde_json::json!({
+                "amount": settings.amount,
+                "radius": settings.radius,
+                "threshold": settings.threshold,
+            }),
+        });
+        effective.output_sharpening = OutputMedium::None;
+    }
+    effective
+}
+
+async fn send_process_request(
+    request: &ProcessingRequest,
+    job_id: &str,
+) -> Result<ProcessResponse, String> {
+    // Call Python backend
+    let client = reqwest::Client::new();
+    let response = client
+        .post("http://localhost:8888/process")
+        .json(&ProcessRequest {
+            job_id,
+            path: &request.input_path,
+            output_path: &request.output_path,
+            operations: &request.nodes,
+            profile: request.profile,
+            folder_id: request.folder_id.as_deref(),
+        })
+        .send()
+        .await
+        .map_err(|e| {
+            let message = format!("Failed to connect to processing engine: {}", e);
+            errors::record_last_error(errors::BACKEND, &message);
+            message
+        })?;
+
+    if !response.status().is_success() {
+        let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+        return Err(format!("Processing engine error: {}", error_text));
+    }
+
+    response
+        .json::<ProcessResponse>()
+        .await
+        .map_err(|e| format!("Invalid response from processing engine: {}", e))
+}
+
+fn remember_request(session_id: &str, request: &ProcessingRequest) {
+    // Store what actually ran, with the input resolved to an absolute path
+    let mut request = request.clone();
+    if let Ok(resolved) = std::fs::canonicalize(&request.input_path) {
+        request.input_path = resolved.to_string_lossy().to_string();
+    }
+
+    let mut history = REQUEST_HISTORY.lock().unwrap();
+    if history.len() >= MAX_REQUEST_HISTORY {
+        history.pop_front();
+    }
+    history.push_back((session_id.to_string(), request));
+}
+
+// The downloaded copy is deleted after the run, so history keeps the URL
+// and a rerun downloads it again
+fn record_source_url(session_id: &str, url: &str) {
+    let mut history = REQUEST_HISTORY.lock().unwrap();
+    if let Some((_, request)) = history.iter_mut().find(|(id, _)| id == session_id) {
+        request.input_path = url.to_string();
+    }
+}
+
+fn is_remote_input(input_path: &str) -> bool {
+    input_path.starts_with("http://") || input_path.starts_with("https://")
+}
+
+fn find_request(session_id: &str) -> Result<ProcessingRequest, String> {
+    REQUEST_HISTORY
+        .lock()
+        .unwrap()
+        .iter()
+        .find(|(id, _)| id == session_id)
+        .map(|(_, request)| request.clone())
+        .ok_or_else(|| format!("No request recorded for session '{}'", session_id))
+}
+
+// Best effort: ask the backend to drop a job we've stopped waiting for
+async fn cancel_backend_job(job_id: &str) {
+    let client = reqwest::Client::new();
+    let _ = client
+        .post(format!("http://localhost:8888/process/{}/cancel", job_id))
+        .send()
+        .await;
+}
+
+pub async fn execute_request(request: &ProcessingRequest) -> Result<ProcessingResult, String> {
+    let result = run_request(request).await;
+    match &result {
+        Err(e) => errors::record_last_error(errors::PIPELINE, e),
+        Ok(ProcessingResult {
+            success: false,
+            error: Some(e),
+            ..
+        }) => errors::record_last_error(errors::PIPELINE, e),
+        Ok(_) => {}
+    }
+    result
+}
+
+// The request's own limit wins over the global default; a limit of zero
+// would time out every job, so only the global setting uses 0 for "off"
+fn max_processing_ms(request: &ProcessingRequest) -> Result<Option<u64>, String> {
+    match request.max_processing_ms {
+        Some(0) => Err("Max processing time must be greater than zero".to_string()),
+        Some(ms) => Ok(Some(ms)),
+        None => Ok(match DEFAULT_MAX_PROCESSING_MS.load(Ordering::Relaxed) {
+            0 => None,
+            ms => Some(ms),
+        }),
+    }
+}
+
+// Err carries the limit that was exceeded
+async fn within_time_limit<F: Future>(limit_ms: Option<u64>, work: F) -> Result<F::Output, u64> {
+    match limit_ms {
+        Some(ms) => tokio::time::timeout(Duration::from_millis(ms), work)
+            .await
+            .map_err(|_| ms),
+        None => Ok(work.await),
+    }
+}
+
+async fn run_request(request: &ProcessingRequest) -> Result<ProcessingResult, String> {
+    // Validate input exists
+    if !Path::new(&request.input_path).exists() {
+        return Err("File not found".to_string());
+    }
+    let max_processing_ms = max_processing_ms(request)?;
+
+    let started = Instant::now();
+    let job_id = format!("job-{}", chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default());
+    let sharpening = output_sharpening(request).await?;
+    let effective = effective_request(request, max_processing_ms, sharpening.as_ref());
+    remember_request(&job_id, &effective);
+
+    let processed = match within_time_limit(
+        max_processing_ms,
+        send_process_request(&effective, &job_id),
+    )
+    .await
+    {
+        Ok(processed) => processed?,
+        Err(ms) => {
+            cancel_backend_job(&job_id).await;
+            return Ok(ProcessingResult {
+                session_id: Some(job_id),
+                success: false,
+                input_path: request.input_path.clone(),
+                input_paths: Vec::new(),
+                output_path: request.output_path.clone(),
+                operations_applied: 0,
+                processing_time_ms: started.elapsed().as_millis() as u64,
+                profile: request.profile,
+                folder_id: request.folder_id.clone(),
+                encoding: None,
+                models: Vec::new(),
+                output_sharpening: None,
+                warning: None,
+                error: Some(format!("Exceeded max processing time of {}ms", ms)),
+            });
+        }
+    };
+
+    let mut result = processed_result(request, job_id, processed, sharpening);
+
+    // Post-process: embed delivery metadata into the finished output
+    if let (true, Some(metadata)) = (result.success, &request.embed_metadata) {
+        if let Err(e) = metadata::embed(&result.output_path, metadata).await {
+            result.warning = Some(format!("Output written but metadata not embedded: {}", e));
+        }
+    }
+
+    result.processing_time_ms = started.elapsed().as_millis() as u64;
+    Ok(result)
+}
+
+// Older backends don't echo folder_id back, so fall back to the request's
+fn processed_result(
+    request: &ProcessingRequest,
+    job_id: String,
+    processed: ProcessResponse,
+    sharpening: Option<SharpeningSettings>,
+) -> ProcessingResult {
+    let success = processed.status == "completed";
+    ProcessingResult {
+        session_id: Some(job_id),
+        success,
+        input_path: request.input_path.clone(),
+        input_paths: Vec::new(),
+        output_path: processed.output_path,
+        operations_applied: processed.operations_applied,
+        processing_time_ms: 0,
+        profile: request.profile,
+        folder_id: processed.folder_id.or_else(|| request.folder_id.clone()),
+        encoding: None,
+        models: processed.models,
+        output_sharpening: sharpening,
+        warning: None,
+        error: if success {
+            None
+        } else {
+            Some(format!("Processing ended with status '{}'", processed.status))
+        },
+    }
+}
+
+// Runs a request whose input_path is an http(s) URL against a temporary download
+async fn execute_from_url(request: &ProcessingRequest) -> Result<ProcessingResult, String> {
+    let url = &request.input_path;
+    let temp_path = download_image(url).await?;
+
+    let mut local = request.clone();
+    local.input_path = temp_path.to_string_lossy().to_string();
+    let result = execute_request(&local).await;
+
+    // Always clean up the downloaded copy
+    let _ = tokio::fs::remove_file(&temp_path).await;
+
+    let mut result = result?;
+    if let Some(session_id) = &result.session_id {
+        record_source_url(session_id, url);
+    }
+    result.input_path = url.clone();
+    Ok(result)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AutoCorrectOptions {
+    pub auto_exposure: bool,
+    pub auto_white_balance: bool,
+    pub auto_contrast: bool,
+}
+
+// Processor types aren't checked against a catalog here: the backend exposes
+// none, so an unknown type surfaces as a processing engine error instead
+fn auto_correct_nodes(options: &AutoCorrectOptions) -> Vec<ProcessingNode> {
+    // Order matters: fix exposure before judging color, contrast last
+    let steps = [
+        (options.auto_exposure, "auto_exposure"),
+        (options.auto_white_balance, "auto_white_balance"),
+        (options.auto_contrast, "auto_contrast"),
+    ];
+
+    steps
+        .iter()
+        .filter(|(enabled, _)| *enabled)
+        .enumerate()
+        .map(|(index, (_, processor_type))| ProcessingNode {
+            id: format!("auto_correct_{}", index),
+            processor_type: processor_type.to_string(),
+            parameters: serde_json::json!({}),
+        })
+        .collect()
+}
+
+fn extension_for_content_type(content_type: &str) -> Option<&'static str> {
+    match content_type {
+        "image/jpeg" | "image/jpg" => Some("jpg"),
+        "image/png" => Some("png"),
+        "image/webp" => Some("webp"),
+        "image/bmp" => Some("bmp"),
+        "image/tiff" => Some("tiff"),
+        _ => None,
+    }
+}
+
+async fn download_image(url: &str) -> Result<PathBuf, String> {
+    let parsed = reqwest::Url::parse(url).map_err(|e| format!("Invalid URL: {}", e))?;
+    if parsed.scheme() != "http" && parsed.scheme() != "https" {
+        return Err("Only http and https URLs are supported".to_string());
+    }
+
+    let client = reqwest::Client::new();
+    let mut response = client
+        .get(parsed)
+        .send()
+        .await
+        .map_err(|e| format!("Download failed: {}", e))?;
+
+    if !response.status().is_success() {
+        return Err(format!("Download failed: server returned {}", response.status()));
+    }
+
+    let content_type = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.split(';').next().unwrap_or("").trim().to_lowercase())
+        .unwrap_or_default();
+    let extension = extension_for_content_type(&content_type)
+        .ok_or_else(|| format!("Download failed: unsupported content type '{}'", content_type))?;
+
+    if response.content_length().unwrap_or(0) > MAX_DOWNLOAD_BYTES {
+        return Err("Download failed: image exceeds maximum download size".to_string());
+    }
+
+    let temp_path = std::env::temp_dir().join(format!(
+        "nstant-download-{}.{}",
+        chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default(),
+        extension
+    ));
+
+    let result = async {
+        let mut file = tokio::fs::File::create(&temp_path)
+            .await
+            .map_err(|e| format!("Download failed: could not create temp file: {}", e))?;
+
+        // Stream to disk, enforcing the size limit even without a Content-Length
+        let mut downloaded: u64 = 0;
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .map_err(|e| format!("Download failed: {}", e))?
+        {
+            downloaded += chunk.len() as u64;
+            if downloaded > MAX_DOWNLOAD_BYTES {
+                return Err("Download failed: image exceeds maximum download size".to_string());
+            }
+            file.write_all(&chunk)
+                .await
+                .map_err(|e| format!("Download failed: could not write temp file: {}", e))?;
+        }
+
+        file.flush()
+            .await
+            .map_err(|e| format!("Download failed: could not write temp file: {}", e))
+    }
+    .await;
+
+    match result {
+        Ok(()) => Ok(temp_path),
+        Err(e) => {
+            let _ = tokio::fs::remove_file(&temp_path).await;
+            Err(e)
+        }
+    }
+}
+
+pub async fn file_checksum(path: &Path) -> Result<String, String> {
+    let bytes = tokio::fs::read(path)
+        .await
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let digest = Sha256::digest(&bytes);
+    Ok(digest.iter().map(|byte| format!("{:02x}", byte)).collect())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeterminismReport {
+    pub runs: usize,
+    pub deterministic: bool,
+    pub checksums: Vec<String>,
+    // Indices of runs whose output differs from run 0
+    pub mismatched_runs: Vec<usize>,
+}
+
+async fn run_determinism_checks(
+    nodes: &[ProcessingNode],
+    input_path: &str,
+    temp_outputs: &[PathBuf],
+) -> Result<Vec<String>, String> {
+    let mut checksums = Vec::with_capacity(temp_outputs.len());
+    for (run, output) in temp_outputs.iter().enumerate() {
+        let request = ProcessingRequest::new(
+            input_path.to_string(),
+            output.to_string_lossy().to_string(),
+            nodes.to_vec(),
+        );
+        let result = execute_request(&request).await?;
+
+        if !result.success {
+            return Err(format!(
+                "Run {} failed: {}",
+                run,
+                result.error.unwrap_or_else(|| "Unknown error".to_string())
+            ));
+        }
+        // Checksum the path this run was asked to write; a backend that reports
+        // some other path (e.g. the input) would make every run look identical
+        if !tokio::fs::try_exists(output).await.unwrap_or(false) {
+            return Err(format!("Run {} did not write {}", run, output.display()));
+        }
+        checksums.push(file_checksum(output).await?);
+    }
+    Ok(checksums)
+}
+
+#[derive(Serialize)]
+struct EncodeRequest<'a> {
+    path: &'a str,
+    output_path: &'a str,
+    format: &'a str,
+    max_bytes: u64,
+    max_iterations: u32,
+}
+
+#[derive(Deserialize)]
+struct EncodeResponse {
+    output_path: String,
+    quality: u8,
+    size_bytes: u64,
+}
+
+// Only lossy formats have a quality setting to search over
+fn target_size_format(format: &str, max_bytes: u64) -> Result<String, String> {
+    if max_bytes == 0 {
+        return Err("Target size must be greater than zero".to_string());
+    }
+    let format = format.to_lowercase();
+    if !matches!(format.as_str(), "jpeg" | "jpg" | "webp") {
+        return Err(format!("Target-size encoding is not supported for '{}'", format));
+    }
+    Ok(format)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum GeometryOp {
+    Rotate { degrees: i32 },
+    FlipH,
+    FlipV,
+    Crop { x: u32, y: u32, width: u32, height: u32 },
+}
+
+// Checks each op against the image size it will actually see, tracking how
+// earlier rotations and crops change the dimensions
+fn geometry_nodes(
+    ops: &[GeometryOp],
+    width: u32,
+    height: u32,
+) -> Result<Vec<ProcessingNode>, String> {
+    let (mut width, mut height) = (width, height);
+    let mut nodes = Vec::with_capacity(ops.len());
+
+    for (index, op) in ops.iter().enumerate() {
+        let (processor_type, parameters) = match *op {
+            GeometryOp::Rotate { degrees } => {
+                if degrees % 90 != 0 || degrees.abs() >= 360 {
+                    return Err(format!(
+                        "Rotation must be a multiple of 90 degrees, got {}",
+                        degrees
+                    ));
+                }
+                if degrees % 180 != 0 {
+                    std::mem::swap(&mut width, &mut height);
+                }
+                ("rotate", serde_json::json!({ "degrees": degrees.rem_euclid(360) }))
+            }
+            GeometryOp::FlipH => ("flip", serde_json::json!({ "axis": "horizontal" })),
+            GeometryOp::FlipV => ("flip", serde_json::json!({ "axis": "vertical" })),
+            GeometryOp::Crop {
+                x,
+                y,
+                width: crop_width,
+                height: crop_height,
+            } => {
+                let fits = crop_width > 0
+                    && crop_height > 0
+                    && x.checked_add(crop_width).is_some_and(|right| right <= width)
+                    && y.checked_add(crop_height).is_some_and(|bottom| bottom <= height);
+                if !fits {
+                    return Err(format!(
+                        "Crop {}x{} at ({}, {}) is outside the {}x{} image",
+                        crop_width, crop_height, x, y, width, height
+                    ));
+                }
+                width = crop_width;
+                height = crop_height;
+                (
+                    "crop",
+                    serde_json::json!({ "x": x, "y": y, "width": crop_width, "height": crop_height }),
+                )
+            }
+        };
+
+        nodes.push(ProcessingNode {
+            id: format!("geometry_{}", index),
+            processor_type: processor_type.to_string(),
+            parameters,
+        });
+    }
+
+    Ok(nodes)
+}
+
+// Tauri commands
+#[tauri::command]
+pub async fn get_request(session_id: String) -> Result<ProcessingRequest, String> {
+    find_request(&session_id)
+}
+
+#[tauri::command]
+pub async fn rerun_request(session_id: String) -> Result<ProcessingResult, String> {
+    let request = find_request(&session_id)?;
+    if is_remote_input(&request.input_path) {
+        return execute_from_url(&request).await;
+    }
+    execute_request(&request).await
+}
+
+#[tauri::command]
+pub async fn set_max_processing_time(max_processing_ms: Option<u64>) -> Result<(), String> {
+    if max_processing_ms == Some(0) {
+        return Err("Max processing time must be greater than zero".to_string());
+    }
+    DEFAULT_MAX_PROCESSING_MS.store(max_processing_ms.unwrap_or(0), Ordering::Relaxed);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_processor_model_info(processor_type: String) -> Result<Option<ModelInfo>, String> {
+    // Call Python backend
+    let client = reqwest::Client::new();
+    let response = client
+        .get(format!("http://localhost:8888/processors/{}/model", processor_type))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to connect to processing engine: {}", e))?;
+
+    if !response.status().is_success() {
+        let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+        return Err(format!("Processing engine error: {}", error_text));
+    }
+
+    let info = response
+        .json::<ModelInfoResponse>()
+        .await
+        .map_err(|e| format!("Invalid response from processing engine: {}", e))?;
+    Ok(info.model)
+}
+
+#[tauri::command]
+pub async fn execute_pipeline(request: ProcessingRequest) -> Result<ProcessingResult, String> {
+    execute_request(&request).await
+}
+
+#[tauri::command]
+pub async fn auto_correct(
+    input_path: String,
+    output_path: String,
+    options: AutoCorrectOptions,
+) -> Result<ProcessingResult, String> {
+    let nodes = auto_correct_nodes(&options);
+    if nodes.is_empty() {
+        return Err("No auto-correction selected".to_string());
+    }
+
+    execute_request(&ProcessingRequest::new(input_path, output_path, nodes)).await
+}
+
+#[tauri::command]
+pub async fn process_image_from_url(
+    url: String,
+    output_path: String,
+    nodes: Vec<ProcessingNode>,
+) -> Result<ProcessingResult, String> {
+    execute_from_url(&ProcessingRequest::new(url, output_path, nodes)).await
+}
+
+#[tauri::command]
+pub async fn verify_determinism(
+    nodes: Vec<ProcessingNode>,
+    input_path: String,
+    runs: usize,
+) -> Result<DeterminismReport, String> {
+    if !(MIN_DETERMINISM_RUNS..=MAX_DETERMINISM_RUNS).contains(&runs) {
+        return Err(format!(
+            "Runs must be between {} and {}",
+            MIN_DETERMINISM_RUNS, MAX_DETERMINISM_RUNS
+        ));
+    }
+
+    let extension = Path::new(&input_path)
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_string())
+        .unwrap_or_else(|| "png".to_string());
+    let stamp = chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default();
+    let temp_outputs: Vec<PathBuf> = (0..runs)
+        .map(|run| {
+            std::env::temp_dir().join(format!("nstant-determinism-{}-{}.{}", stamp, run, extension))
+        })
+        .collect();
+
+    let checksums = run_determinism_checks(&nodes, &input_path, &temp_outputs).await;
+
+    // Clean up every run's output, even if a later run failed
+    for output in &temp_outputs {
+        let _ = tokio::fs::remove_file(output).await;
+    }
+
+    let checksums = checksums?;
+    let mismatched_runs: Vec<usize> = checksums
+        .iter()
+        .enumerate()
+        .skip(1)
+        .filter(|(_, checksum)| **checksum != checksums[0])
+        .map(|(run, _)| run)
+        .collect();
+
+    Ok(DeterminismReport {
+        runs,
+        deterministic: mismatched_runs.is_empty(),
+        checksums,
+        mismatched_runs,
+    })
+}
+
+#[tauri::command]
+pub async fn encode_to_target_size(
+    input_path: String,
+    output_path: String,
+    max_bytes: u64,
+    format: String,
+) -> Result<ProcessingResult, String> {
+    if !Path::new(&input_path).exists() {
+        return Err("File not found".to_string());
+    }
+    let format = target_size_format(&format, max_bytes)?;
+
+    let started = Instant::now();
+
+    // Call Python backend, which binary-searches the quality setting
+    let client = reqwest::Client::new();
+    let response = client
+        .post("http://localhost:8888/encode/target-size")
+        .json(&EncodeRequest {
+            path: &input_path,
+            output_path: &output_path,
+            format: &format,
+            max_bytes,
+            max_iterations: MAX_ENCODE_ITERATIONS,
+        })
+        .send()
+        .await
+        .map_err(|e| format!("Failed to connect to processing engine: {}", e))?;
+
+    if !response.status().is_success() {
+        let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+        return Err(format!("Processing engine error: {}", error_text));
+    }
+
+    let encoded = response
+        .json::<EncodeResponse>()
+        .await
+        .map_err(|e| format!("Invalid response from processing engine: {}", e))?;
+
+    // Trust the file on disk over the reported size when we can see it
+    let size_bytes = tokio::fs::metadata(&encoded.output_path)
+        .await
+        .map(|metadata| metadata.len())
+        .unwrap_or(encoded.size_bytes);
+    let encoding = EncodingOutcome::new(format, encoded.quality, size_bytes, max_bytes);
+
+    // The smallest achievable file is still written, but it missed the target
+    Ok(ProcessingResult {
+        session_id: None,
+        success: encoding.target_met,
+        input_path,
+        input_paths: Vec::new(),
+        output_path: encoded.output_path,
+        operations_applied: 1,
+        processing_time_ms: started.elapsed().as_millis() as u64,
+        profile: ProcessingProfile::default(),
+        folder_id: None,
+        models: Vec::new(),
+        output_sharpening: None,
+        warning: encoding.warning(),
+        encoding: Some(encoding),
+        error: None,
+    })
+}
+
+#[tauri::command]
+pub async fn apply_geometry(
+    input_path: String,
+    output_path: String,
+    ops: Vec<GeometryOp>,
+) -> Result<ProcessingResult, String> {
+    if ops.is_empty() {
+        return Err("No geometry operations given".to_string());
+    }
+
+    let info = get_image_info(input_path.clone()).await?;
+    let nodes = geometry_nodes(&ops, info.width, info.height)?;
+
+    execute_request(&ProcessingRequest::new(input_path, output_path, nodes)).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn processor_types(nodes: &[ProcessingNode]) -> Vec<&str> {
+        nodes.iter().map(|node| node.processor_type.as_str()).collect()
+    }
+
+    #[test]
+    fn auto_correct_nodes_follow_options_in_order() {
+        let all = AutoCorrectOptions {
+            auto_exposure: true,
+            auto_white_balance: true,
+            auto_contrast: true,
+        };
+        let nodes = auto_correct_nodes(&all);
+        assert_eq!(
+            processor_types(&nodes),
+            ["auto_exposure", "auto_white_balance", "auto_contrast"]
+        );
+        assert_eq!(nodes[2].id, "auto_correct_2");
+
+        let contrast_only = AutoCorrectOptions {
+            auto_exposure: false,
+            auto_white_balance: false,
+            auto_contrast: true,
+        };
+        let nodes = auto_correct_nodes(&contrast_only);
+        assert_eq!(processor_types(&nodes), ["auto_contrast"]);
+        assert_eq!(nodes[0].id, "auto_correct_0");
+
+        let none = AutoCorrectOptions {
+            auto_exposure: false,
+            auto_white_balance: false,
+            auto_contrast: false,
+        };
+        assert!(auto_correct_nodes(&none).is_empty());
+    }
+
+    #[test]
+    fn folder_id_round_trips_through_the_backend() {
+        let mut request =
+            ProcessingRequest::new("in.jpg".to_string(), "out.jpg".to_string(), Vec::new());
+        request.folder_id = Some("studio-a".to_string());
+
+        let body = serde_json::to_value(ProcessRequest {
+            job_id: "job-1",
+            path: &request.input_path,
+            output_path: &request.output_path,
+            operations: &request.nodes,
+            profile: request.profile,
+            folder_id: request.folder_id.as_deref(),
+        })
+        .unwrap();
+        assert_eq!(body["folder_id"], "studio-a");
+
+        let echoed: ProcessResponse = serde_json::from_value(serde_json::json!({
+            "status": "completed",
+            "output_path": "out.jpg",
+            "operations_applied": 0,
+            "folder_id": body["folder_id"],
+        }))
+        .unwrap();
+        let result = processed_result(&request, "job-1".to_string(), echoed, None);
+        assert_eq!(result.folder_id.as_deref(), Some("studio-a"));
+
+        // A backend that drops the field still attributes the result
+        let legacy: ProcessResponse = serde_json::from_value(serde_json::json!({
+            "status": "completed",
+            "output_path": "out.jpg",
+            "operations_applied": 0,
+        }))
+        .unwrap();
+        let result = processed_result(&request, "job-1".to_string(), legacy, None);
+        assert_eq!(result.folder_id.as_deref(), Some("studio-a"));
+    }
+
+    #[test]
+    fn url_requests_are_recorded_with_their_source_url() {
+        let download = std::env::temp_dir().join("nstant-history-test.jpg");
+        let mut request = ProcessingRequest::new(
+            download.to_string_lossy().to_string(),
+            "out.jpg".to_string(),
+            Vec::new(),
+        );
+        request.output_sharpening = OutputMedium::Web;
+        let sharpening = OutputMedium::Web.sharpening(6000);
+        let effective = effective_request(&request, Some(30_000), sharpening.as_ref());
+        remember_request("job-history-test", &effective);
+        record_source_url("job-history-test", "https://example.com/photo.jpg");
+
+        let recorded = find_request("job-history-test").unwrap();
+        assert_eq!(recorded.input_path, "https://example.com/photo.jpg");
+        assert!(is_remote_input(&recorded.input_path));
+        assert!(!is_remote_input(&download.to_string_lossy()));
+
+        // The merged limit and resolved sharpening are what gets rerun
+        assert_eq!(recorded.max_processing_ms, Some(30_000));
+        assert_eq!(recorded.output_sharpening, OutputMedium::None);
+        let node = recorded.nodes.last().unwrap();
+        assert_eq!(node.id, "output_sharpening");
+        assert_eq!(node.parameters["amount"], 0.5);
+        assert_eq!(node.parameters["radius"], 1.0);
+    }
+
+    #[test]
+    fn model_info_is_optional() {
+        let present: ModelInfoResponse = serde_json::from_str(
+            r#"{"model": {"name": "denoise-net", "version": "2.1.0", "hash": "sha256:ab12"}}"#,
+        )
+        .unwrap();
+        let model = present.model.unwrap();
+        assert_eq!(model.name, "denoise-net");
+        assert_eq!(model.version, "2.1.0");
+        assert_eq!(model.hash, "sha256:ab12");
+
+        for absent in ["{}", r#"{"model": null}"#] {
+            let response: ModelInfoResponse = serde_json::from_str(absent).unwrap();
+            assert!(response.model.is_none());
+        }
+    }
+
+    #[test]
+    fn results_list_the_models_used_per_node() {
+        let request = ProcessingRequest::new("in.jpg".to_string(), "out.jpg".to_string(), Vec::new());
+        let with_models: ProcessResponse = serde_json::from_value(serde_json::json!({
+            "status": "completed",
+            "output_path": "out.jpg",
+            "operations_applied": 2,
+            "models": [{
+                "node_id": "denoise_1",
+                "model": {"name": "denoise-net", "version": "2.1.0", "hash": "sha256:ab12"},
+            }],
+        }))
+        .unwrap();
+        let result = processed_result(&request, "job-1".to_string(), with_models, None);
+        assert_eq!(result.models.len(), 1);
+        assert_eq!(result.models[0].node_id, "denoise_1");
+        assert_eq!(result.models[0].model.version, "2.1.0");
+
+        let without_models: ProcessResponse = serde_json::from_value(serde_json::json!({
+            "status": "completed",
+            "output_path": "out.jpg",
+            "operations_applied": 1,
+        }))
+        .unwrap();
+        let result = processed_result(&request, "job-2".to_string(), without_models, None);
+        assert!(result.models.is_empty());
+    }
+
+    #[test]
+    fn crop_after_rotation_uses_rotated_dimensions() {
+        // 4000x3000 landscape rotated to 3000x4000 portrait
+        let ops = [
+            GeometryOp::Rotate { degrees: -90 },
+            GeometryOp::Crop { x: 0, y: 1000, width: 3000, height: 3000 },
+            GeometryOp::FlipH,
+        ];
+        let nodes = geometry_nodes(&ops, 4000, 3000).unwrap();
+        assert_eq!(processor_types(&nodes), ["rotate", "crop", "flip"]);
+        assert_eq!(nodes[0].parameters["degrees"], 270);
+        assert_eq!(nodes[1].id, "geometry_1");
+        assert_eq!(nodes[1].parameters["height"], 3000);
+
+        // The same crop doesn't fit the unrotated image
+        let unrotated = [GeometryOp::Crop { x: 0, y: 1000, width: 3000, height: 3000 }];
+        assert_eq!(
+            geometry_nodes(&unrotated, 4000, 3000).unwrap_err(),
+            "Crop 3000x3000 at (0, 1000) is outside the 4000x3000 image"
+        );
+    }
+
+    #[test]
+    fn out_of_bounds_crops_are_rejected() {
+        let crop = |x, y, width, height| [GeometryOp::Crop { x, y, width, height }];
+        assert!(geometry_nodes(&crop(0, 0, 100, 100), 100, 100).is_ok());
+        assert!(geometry_nodes(&crop(1, 0, 100, 100), 100, 100).is_err());
+        assert!(geometry_nodes(&crop(0, 0, 0, 50), 100, 100).is_err());
+        assert!(geometry_nodes(&crop(u32::MAX, 0, 1, 1), 100, 100).is_err());
+
+        // A later crop is checked against the earlier crop's size
+        let chained = [
+            GeometryOp::Crop { x: 0, y: 0, width: 50, height: 50 },
+            GeometryOp::Crop { x: 25, y: 25, width: 30, height: 10 },
+        ];
+        assert_eq!(
+            geometry_nodes(&chained, 100, 100).unwrap_err(),
+            "Crop 30x10 at (25, 25) is outside the 50x50 image"
+        );
+    }
+
+    #[test]
+    fn rotations_must_be_quarter_turns() {
+        assert!(geometry_nodes(&[GeometryOp::Rotate { degrees: 45 }], 10, 10).is_err());
+        assert!(geometry_nodes(&[GeometryOp::Rotate { degrees: 360 }], 10, 10).is_err());
+        assert!(geometry_nodes(&[GeometryOp::Rotate { degrees: 180 }], 10, 10).is_ok());
+    }
+
+    #[tokio::test]
+    async fn work_past_the_time_limit_times_out() {
+        let stalled = within_time_limit(Some(20), std::future::pending::<()>()).await;
+        assert_eq!(stalled, Err(20));
+
+        assert_eq!(within_time_limit(Some(1_000), async { 7 }).await, Ok(7));
+        assert_eq!(within_time_limit(None, async { 7 }).await, Ok(7));
+    }
+
+    #[test]
+    fn zero_request_time_limit_is_rejected() {
+        let mut request =
+            ProcessingRequest::new("in.jpg".to_string(), "out.jpg".to_string(), Vec::new());
+        assert_eq!(max_processing_ms(&request), Ok(None));
+
+        request.max_processing_ms = Some(500);
+        assert_eq!(max_processing_ms(&request), Ok(Some(500)));
+
+        request.max_processing_ms = Some(0);
+        assert_eq!(
+            max_processing_ms(&request),
+            Err("Max processing time must be greater than zero".to_string())
+        );
+    }
+
+    #[test]
+    fn encoding_target_is_met_up_to_and_including_max_bytes() {
+        let under = EncodingOutcome::new("jpeg".to_string(), 82, 1_999_999, 2_000_000);
+        assert!(under.target_met);
+        assert_eq!(under.warning(), None);
+
+        let exact = EncodingOutcome::new("jpeg".to_string(), 85, 2_000_000, 2_000_000);
+        assert!(exact.target_met);
+        assert_eq!(exact.warning(), None);
+
+        let over = EncodingOutcome::new("jpeg".to_string(), 1, 2_000_001, 2_000_000);
+        assert!(!over.target_met);
+        assert_eq!(
+            over.warning().unwrap(),
+            "Smallest achievable size is 2000001 bytes, above the 2000000 byte target"
+        );
+    }
+
+    #[test]
+    fn target_size_encoding_needs_a_lossy_format_and_nonzero_target() {
+        assert_eq!(target_size_format("JPEG", 1).unwrap(), "jpeg");
+        assert_eq!(target_size_format("webp", u64::MAX).unwrap(), "webp");
+        assert_eq!(
+            target_size_format("jpg", 0).unwrap_err(),
+            "Target size must be greater than zero"
+        );
+        assert_eq!(
+            target_size_format("PNG", 1_000).unwrap_err(),
+            "Target-size encoding is not supported for 'png'"
+        );
+    }
+}
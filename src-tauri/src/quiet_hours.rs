@@ -0,0 +1,269 @@
+use crate::errors;
+use crate::hot_folder::HotFolderManager;
+use chrono::{DateTime, Datelike, Duration as ChronoDuration, Local, NaiveTime, Weekday};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, State};
+use tokio::sync::watch;
+
+pub const PROCESSING_PAUSED_EVENT: &str = "processing-paused";
+pub const PROCESSING_RESUMED_EVENT: &str = "processing-resumed";
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+// Times are local "HH:MM". A window whose end is before its start runs past
+// midnight and belongs to the day it starts on. Empty days means every day.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuietHours {
+    pub start: String,
+    pub end: String,
+    #[serde(default)]
+    pub days: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProcessingPauseEvent {
+    pub reason: String,
+    pub held: usize,
+}
+
+struct QuietWindow {
+    start: NaiveTime,
+    end: NaiveTime,
+    days: Vec<Weekday>,
+}
+
+impl QuietWindow {
+    fn parse(config: &QuietHours) -> Result<Self, String> {
+        let time = |value: &str| {
+            NaiveTime::parse_from_str(value.trim(), "%H:%M")
+                .map_err(|_| format!("Invalid quiet hours time '{}', expected HH:MM", value))
+        };
+        let start = time(&config.start)?;
+        let end = time(&config.end)?;
+        if start == end {
+            return Err("Quiet hours start and end must differ".to_string());
+        }
+
+        let days = config
+            .days
+            .iter()
+            .map(|day| {
+                day.trim()
+                    .parse::<Weekday>()
+                    .map_err(|_| format!("Invalid quiet hours day '{}'", day))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self { start, end, days })
+    }
+
+    fn applies_on(&self, day: Weekday) -> bool {
+        self.days.is_empty() || self.days.contains(&day)
+    }
+
+    fn contains(&self, now: DateTime<Local>) -> bool {
+        let time = now.time();
+        if self.start < self.end {
+            return self.applies_on(now.weekday()) && time >= self.start && time < self.end;
+        }
+        // Spans midnight: the evening part today, or the morning tail of yesterday's window
+        let yesterday = (now - ChronoDuration::days(1)).weekday();
+        (self.applies_on(now.weekday()) && time >= self.start)
+            || (self.applies_on(yesterday) && time < self.end)
+    }
+}
+
+// Best effort: the backend may be down, in which case held submissions still
+// keep new work away from it
+async fn set_backend_queue_paused(paused: bool) {
+    let action = if paused { "pause" } else { "resume" };
+    let client = reqwest::Client::new();
+    let result = client
+        .post(format!("http://localhost:8888/queue/{}", action))
+        .send()
+        .await;
+    match result {
+        Ok(response) if response.status().is_success() => {}
+        Ok(response) => errors::record_last_error(
+            errors::BACKEND,
+            &format!("Failed to {} backend queue: {}", action, response.status()),
+        ),
+        Err(e) => errors::record_last_error(
+            errors::BACKEND,
+            &format!("Failed to connect to processing engine: {}", e),
+        ),
+    }
+}
+
+// A manual hold placed during the window stays in place; its files are
+// flushed when the user releases it
+async fn release(manager: &HotFolderManager, app_handle: &AppHandle) {
+    set_backend_queue_paused(false).await;
+    let held = manager.release_for_quiet_hours().await;
+    let _ = app_handle.emit(
+        PROCESSING_RESUMED_EVENT,
+        &ProcessingPauseEvent {
+            reason: "quiet_hours".to_string(),
+            held,
+        },
+    );
+}
+
+pub struct QuietHoursScheduler {
+    config: Mutex<Option<QuietHours>>,
+    // Dropping the sender stops the running task between checks. It is never
+    // aborted, so a release in progress always finishes its flush.
+    stop: Mutex<Option<watch::Sender<()>>>,
+    // Set while the scheduler's own hold is in place
+    paused: Arc<AtomicBool>,
+    app_handle: AppHandle,
+}
+
+impl QuietHoursScheduler {
+    pub fn new(app_handle: AppHandle) -> Self {
+        Self {
+            config: Mutex::new(None),
+            stop: Mutex::new(None),
+            paused: Arc::new(AtomicBool::new(false)),
+            app_handle,
+        }
+    }
+
+    pub fn get(&self) -> Option<QuietHours> {
+        self.config.lock().unwrap().clone()
+    }
+
+    pub fn set(&self, config: Option<QuietHours>) -> Result<(), String> {
+        let window = config.as_ref().map(QuietWindow::parse).transpose()?;
+
+        let (stop, mut stopped) = watch::channel(());
+        // Replacing the sender stops the previous task
+        *self.stop.lock().unwrap() = Some(stop);
+        *self.config.lock().unwrap() = config;
+
+        let app_handle = self.app_handle.clone();
+        let paused = self.paused.clone();
+        tokio::spawn(async move {
+            let manager = app_handle.state::<Arc<HotFolderManager>>().inner().clone();
+            loop {
+                let quiet = window.as_ref().is_some_and(|window| window.contains(Local::now()));
+
+                if quiet && !paused.load(Ordering::SeqCst) {
+                    manager.hold_for_quiet_hours();
+                    paused.store(true, Ordering::SeqCst);
+                    set_backend_queue_paused(true).await;
+                    let _ = app_handle.emit(
+                        PROCESSING_PAUSED_EVENT,
+                        &ProcessingPauseEvent {
+                            reason: "quiet_hours".to_string(),
+                            held: manager.held_submission_count(),
+                        },
+                    );
+                } else if !quiet && paused.swap(false, Ordering::SeqCst) {
+                    release(&manager, &app_handle).await;
+                }
+
+                // Nothing left to schedule once cleared and released
+                if window.is_none() {
+                    break;
+                }
+                tokio::select! {
+                    _ = tokio::time::sleep(CHECK_INTERVAL) => {}
+                    _ = stopped.changed() => break,
+                }
+            }
+        });
+
+        Ok(())
+    }
+}
+
+// Tauri commands
+#[tauri::command]
+pub async fn set_quiet_hours(
+    quiet_hours: Option<QuietHours>,
+    scheduler: State<'_, Arc<QuietHoursScheduler>>,
+) -> Result<(), String> {
+    scheduler.set(quiet_hours)
+}
+
+#[tauri::command]
+pub async fn get_quiet_hours(
+    scheduler: State<'_, Arc<QuietHoursScheduler>>,
+) -> Result<Option<QuietHours>, String> {
+    Ok(scheduler.get())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn quiet_hours(start: &str, end: &str, days: &[&str]) -> QuietHours {
+        QuietHours {
+            start: start.to_string(),
+            end: end.to_string(),
+            days: days.iter().map(|day| day.to_string()).collect(),
+        }
+    }
+
+    // 2026-10-12 is a Monday
+    fn at(day: u32, hour: u32, minute: u32) -> DateTime<Local> {
+        Local.with_ymd_and_hms(2026, 10, day, hour, minute, 0).unwrap()
+    }
+
+    #[test]
+    fn invalid_windows_are_rejected() {
+        assert!(QuietWindow::parse(&quiet_hours("22:00", "06:00", &["mon", "Friday"])).is_ok());
+        assert_eq!(
+            QuietWindow::parse(&quiet_hours("10pm", "06:00", &[])).err().unwrap(),
+            "Invalid quiet hours time '10pm', expected HH:MM"
+        );
+        assert_eq!(
+            QuietWindow::parse(&quiet_hours("22:00", "22:00", &[])).err().unwrap(),
+            "Quiet hours start and end must differ"
+        );
+        assert_eq!(
+            QuietWindow::parse(&quiet_hours("22:00", "06:00", &["someday"])).err().unwrap(),
+            "Invalid quiet hours day 'someday'"
+        );
+    }
+
+    #[test]
+    fn same_day_window_includes_start_and_excludes_end() {
+        let window = QuietWindow::parse(&quiet_hours("12:00", "14:00", &[])).unwrap();
+        assert!(!window.contains(at(12, 11, 59)));
+        assert!(window.contains(at(12, 12, 0)));
+        assert!(window.contains(at(15, 13, 59)));
+        assert!(!window.contains(at(12, 14, 0)));
+    }
+
+    #[test]
+    fn window_crossing_midnight_covers_both_sides() {
+        let window = QuietWindow::parse(&quiet_hours("22:00", "06:00", &[])).unwrap();
+        assert!(window.contains(at(12, 23, 30)));
+        assert!(window.contains(at(13, 0, 0)));
+        assert!(window.contains(at(13, 5, 59)));
+        assert!(!window.contains(at(13, 6, 0)));
+        assert!(!window.contains(at(13, 21, 59)));
+    }
+
+    #[test]
+    fn day_filter_follows_the_day_the_window_starts() {
+        let window = QuietWindow::parse(&quiet_hours("22:00", "06:00", &["mon"])).unwrap();
+        // Monday night and the Tuesday morning tail belong to Monday's window
+        assert!(window.contains(at(12, 22, 30)));
+        assert!(window.contains(at(13, 3, 0)));
+        // Monday morning is the tail of Sunday's window, which isn't selected
+        assert!(!window.contains(at(12, 3, 0)));
+        assert!(!window.contains(at(13, 22, 30)));
+
+        let daytime = QuietWindow::parse(&quiet_hours("09:00", "17:00", &["sat", "sun"])).unwrap();
+        assert!(!window.applies_on(Weekday::Tue));
+        assert!(daytime.contains(at(17, 10, 0)));
+        assert!(!daytime.contains(at(16, 10, 0)));
+    }
+}
@@ -0,0 +1,238 @@
+use crate::commands::{check_backend_health, get_image_info};
+use crate::pipeline::{execute_request, ProcessingRequest};
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::path::Path;
+use std::time::Instant;
+
+// 8x8 RGB gradient, small enough to keep the whole run well under a second
+const SMOKE_TEST_IMAGE: &[u8] = include_bytes!("../assets/smoke-test.png");
+const SMOKE_TEST_SIZE: (u32, u32) = (8, 8);
+const SMOKE_TEST_FOLDER_ID: &str = "smoke-test";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SmokeTestStage {
+    pub name: String,
+    pub passed: bool,
+    pub duration_ms: u64,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SmokeTestReport {
+    pub passed: bool,
+    // Stages run in order and stop at the first failure
+    pub stages: Vec<SmokeTestStage>,
+    pub total_time_ms: u64,
+}
+
+#[derive(Deserialize)]
+struct QueuedItem {
+    id: String,
+}
+
+async fn run_stage<T>(
+    stages: &mut Vec<SmokeTestStage>,
+    name: &str,
+    stage: impl Future<Output = Result<T, String>>,
+) -> Option<T> {
+    let started = Instant::now();
+    let outcome = stage.await;
+    stages.push(SmokeTestStage {
+        name: name.to_string(),
+        passed: outcome.is_ok(),
+        duration_ms: started.elapsed().as_millis() as u64,
+        error: outcome.as_ref().err().cloned(),
+    });
+    outcome.ok()
+}
+
+async fn check_health() -> Result<(), String> {
+    match check_backend_health().await? {
+        true => Ok(()),
+        false => Err("Processing engine is not healthy".to_string()),
+    }
+}
+
+async fn check_image(path: &str) -> Result<(), String> {
+    let info = get_image_info(path.to_string()).await?;
+    if (info.width, info.height) != SMOKE_TEST_SIZE {
+        return Err(format!(
+            "{} decoded as {}x{}, expected {}x{}",
+            path, info.width, info.height, SMOKE_TEST_SIZE.0, SMOKE_TEST_SIZE.1
+        ));
+    }
+    Ok(())
+}
+
+// Round-trips an item through the backend queue without leaving it there
+async fn check_queue(path: &str) -> Result<(), String> {
+    let client = reqwest::Client::new();
+    let response = client
+        .post("http://localhost:8888/queue/add")
+        .json(&serde_json::json!({
+            "path": path,
+            "folder_id": SMOKE_TEST_FOLDER_ID,
+            "priority": "low"
+        }))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to connect to processing engine: {}", e))?;
+
+    if !response.status().is_success() {
+        let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+        return Err(format!("Processing engine error: {}", error_text));
+    }
+
+    let item = response
+        .json::<QueuedItem>()
+        .await
+        .map_err(|e| format!("Invalid response from processing engine: {}", e))?;
+
+    let response = client
+        .delete(format!("http://localhost:8888/queue/item/{}", item.id))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to connect to processing engine: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("Could not remove queue item {}", item.id));
+    }
+    Ok(())
+}
+
+// The backend reporting success is not enough; the output must actually be
+// written to the requested path, not echoed back from the input
+fn check_output(
+    input_path: &str,
+    output_path: &str,
+    reported_path: &str,
+    written: bool,
+) -> Result<(), String> {
+    if Path::new(reported_path) == Path::new(input_path) {
+        return Err(format!(
+            "Processing engine returned the input {} instead of writing {}",
+            input_path, output_path
+        ));
+    }
+    if !written {
+        return Err(format!("Processing engine did not write {}", output_path));
+    }
+    Ok(())
+}
+
+async fn process(input_path: &str, output_path: &str) -> Result<(), String> {
+    let request =
+        ProcessingRequest::new(input_path.to_string(), output_path.to_string(), Vec::new());
+    let result = execute_request(&request).await?;
+    if let Some(error) = result.error {
+        return Err(error);
+    }
+    let written = tokio::fs::try_exists(output_path).await.unwrap_or(false);
+    check_output(input_path, output_path, &result.output_path, written)
+}
+
+async fn run_stages(dir: &Path, stages: &mut Vec<SmokeTestStage>) -> Option<()> {
+    let input_path = dir.join("input.png").to_string_lossy().to_string();
+    let output_path = dir.join("output.png").to_string_lossy().to_string();
+
+    run_stage(stages, "backend_health", check_health()).await?;
+    run_stage(stages, "write_input", async {
+        tokio::fs::create_dir_all(dir)
+            .await
+            .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+        tokio::fs::write(&input_path, SMOKE_TEST_IMAGE)
+            .await
+            .map_err(|e| format!("Failed to write test image: {}", e))
+    })
+    .await?;
+    run_stage(stages, "read_input", check_image(&input_path)).await?;
+    run_stage(stages, "queue", check_queue(&input_path)).await?;
+    run_stage(stages, "process", process(&input_path, &output_path)).await?;
+    run_stage(stages, "verify_output", check_image(&output_path)).await
+}
+
+// Tauri commands
+#[tauri::command]
+pub async fn run_smoke_test() -> Result<SmokeTestReport, String> {
+    let started = Instant::now();
+    let dir = std::env::temp_dir().join(format!(
+        "nstant-smoke-test-{}",
+        chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default()
+    ));
+
+    let mut stages = Vec::new();
+    let passed = run_stages(&dir, &mut stages).await.is_some();
+
+    // Clean up everything the run created, whatever the outcome
+    let _ = tokio::fs::remove_dir_all(&dir).await;
+
+    Ok(SmokeTestReport {
+        passed,
+        stages,
+        total_time_ms: started.elapsed().as_millis() as u64,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn passing() -> Result<(), String> {
+        Ok(())
+    }
+
+    async fn failing() -> Result<(), String> {
+        Err("backend down".to_string())
+    }
+
+    fn names(stages: &[SmokeTestStage]) -> Vec<&str> {
+        stages.iter().map(|stage| stage.name.as_str()).collect()
+    }
+
+    #[tokio::test]
+    async fn stages_are_recorded_in_the_order_they_run() {
+        let mut stages = Vec::new();
+        let ran = async {
+            run_stage(&mut stages, "first", passing()).await?;
+            run_stage(&mut stages, "second", passing()).await?;
+            run_stage(&mut stages, "third", passing()).await
+        }
+        .await;
+
+        assert!(ran.is_some());
+        assert_eq!(names(&stages), ["first", "second", "third"]);
+        assert!(stages.iter().all(|stage| stage.passed && stage.error.is_none()));
+    }
+
+    #[tokio::test]
+    async fn a_failed_stage_stops_the_run() {
+        let mut stages = Vec::new();
+        let ran = async {
+            run_stage(&mut stages, "first", passing()).await?;
+            run_stage(&mut stages, "second", failing()).await?;
+            run_stage(&mut stages, "third", passing()).await
+        }
+        .await;
+
+        assert!(ran.is_none());
+        assert_eq!(names(&stages), ["first", "second"]);
+        assert!(!stages[1].passed);
+        assert_eq!(stages[1].error.as_deref(), Some("backend down"));
+    }
+
+    #[test]
+    fn output_must_be_written_to_the_requested_path() {
+        let dir = Path::new("smoke");
+        let input = dir.join("input.png").to_string_lossy().to_string();
+        let output = dir.join("output.png").to_string_lossy().to_string();
+
+        assert!(check_output(&input, &output, &output, true).is_ok());
+        assert!(check_output(&input, &output, &output, false)
+            .unwrap_err()
+            .contains("did not write"));
+        // A backend that echoes the input back has not processed anything
+        assert!(check_output(&input, &output, &input, true)
+            .unwrap_err()
+            .contains("returned the input"));
+    }
+}
@@ -0,0 +1,338 @@
+use crate::errors;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, State};
+use tokio::sync::watch;
+
+pub const QUEUE_ETA_EVENT: &str = "queue-eta";
+
+// Rates are averaged over the completions seen in this trailing window
+const THROUGHPUT_WINDOW: Duration = Duration::from_secs(60);
+const ETA_INTERVAL: Duration = Duration::from_secs(5);
+
+// Completion counts sampled from the backend queue over time
+static THROUGHPUT: Mutex<ThroughputTracker> = Mutex::new(ThroughputTracker::new());
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThroughputStats {
+    pub images_per_second: f64,
+    pub bytes_per_second: f64,
+    pub window_secs: u64,
+    pub completed_in_window: u64,
+    // Items still pending or processing in the backend queue
+    pub queue_depth: u64,
+    // None until there is a recent rate to extrapolate from
+    pub eta_seconds: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueueEta {
+    pub queue_depth: u64,
+    // Seconds until the queue drains; None while still estimating
+    pub eta_seconds: Option<f64>,
+    pub estimating: bool,
+    pub images_per_second: f64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct QueueCounts {
+    pub pending_items: u64,
+    pub processing_items: u64,
+    pub completed_items: u64,
+    #[serde(default)]
+    pub completed_bytes: u64,
+}
+
+impl QueueCounts {
+    pub fn depth(&self) -> u64 {
+        self.pending_items + self.processing_items
+    }
+}
+
+struct Sample {
+    at: Instant,
+    completed: u64,
+    bytes: u64,
+}
+
+// Ring buffer of completion samples; the rate is the change in the backend's
+// completed counters between the oldest sample in the window and the newest
+pub(crate) struct ThroughputTracker {
+    samples: VecDeque<Sample>,
+}
+
+impl ThroughputTracker {
+    pub const fn new() -> Self {
+        Self {
+            samples: VecDeque::new(),
+        }
+    }
+
+    pub fn record(&mut self, at: Instant, counts: &QueueCounts) {
+        // The counters only go down when the queue was cleared or items were
+        // removed; start measuring again from here
+        if self
+            .samples
+            .back()
+            .is_some_and(|last| counts.completed_items < last.completed)
+        {
+            self.samples.clear();
+        }
+        self.samples.push_back(Sample {
+            at,
+            completed: counts.completed_items,
+            bytes: counts.completed_bytes,
+        });
+
+        // Keep one sample at or before the window start as the baseline
+        while self.samples.len() > 1
+            && at.duration_since(self.samples[1].at) >= THROUGHPUT_WINDOW
+        {
+            self.samples.pop_front();
+        }
+    }
+
+    pub fn stats(&self, queue_depth: u64) -> ThroughputStats {
+        let mut stats = ThroughputStats {
+            images_per_second: 0.0,
+            bytes_per_second: 0.0,
+            window_secs: THROUGHPUT_WINDOW.as_secs(),
+            completed_in_window: 0,
+            queue_depth,
+            eta_seconds: None,
+        };
+        let (Some(first), Some(last)) = (self.samples.front(), self.samples.back()) else {
+            return stats;
+        };
+        let elapsed = last.at.duration_since(first.at).as_secs_f64();
+        let completed = last.completed - first.completed;
+        if elapsed <= 0.0 || completed == 0 {
+            return stats;
+        }
+
+        stats.completed_in_window = completed;
+        stats.images_per_second = completed as f64 / elapsed;
+        stats.bytes_per_second = last.bytes.saturating_sub(first.bytes) as f64 / elapsed;
+        stats.eta_seconds = Some(queue_depth as f64 / stats.images_per_second);
+        stats
+    }
+}
+
+pub(crate) async fn fetch_queue_counts() -> Result<QueueCounts, String> {
+    let client = reqwest::Client::new();
+    let response = client
+        .get("http://localhost:8888/queue/status")
+        .send()
+        .await
+        .map_err(|e| format!("Failed to connect to processing engine: {}", e))?;
+
+    if !response.status().is_success() {
+        let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+        return Err(format!("Processing engine error: {}", error_text));
+    }
+
+    response
+        .json::<QueueCounts>()
+        .await
+        .map_err(|e| format!("Invalid response from processing engine: {}", e))
+}
+
+// Samples the backend queue now and reports the rate over the window
+pub(crate) async fn sample_throughput() -> Result<ThroughputStats, String> {
+    let counts = fetch_queue_counts().await?;
+    let mut tracker = THROUGHPUT.lock().unwrap();
+    tracker.record(Instant::now(), &counts);
+    Ok(tracker.stats(counts.depth()))
+}
+
+// Emitted while the queue has work, plus one final zero once it drains so
+// the countdown can clear; nothing while the queue stays empty
+fn eta_event(stats: &ThroughputStats, was_draining: bool) -> Option<QueueEta> {
+    if stats.queue_depth == 0 {
+        return was_draining.then_some(QueueEta {
+            queue_depth: 0,
+            eta_seconds: Some(0.0),
+            estimating: false,
+            images_per_second: stats.images_per_second,
+        });
+    }
+    Some(QueueEta {
+        queue_depth: stats.queue_depth,
+        eta_seconds: stats.eta_seconds,
+        estimating: stats.eta_seconds.is_none(),
+        images_per_second: stats.images_per_second,
+    })
+}
+
+pub struct QueueEtaMonitor {
+    stop: Mutex<Option<watch::Sender<()>>>,
+    app_handle: AppHandle,
+}
+
+impl QueueEtaMonitor {
+    pub fn new(app_handle: AppHandle) -> Self {
+        Self {
+            stop: Mutex::new(None),
+            app_handle,
+        }
+    }
+
+    // Replacing the sender stops the previous task, so there is only ever one
+    pub fn start(&self) {
+        let (stop, mut stopped) = watch::channel(());
+        *self.stop.lock().unwrap() = Some(stop);
+
+        let app_handle = self.app_handle.clone();
+        tokio::spawn(async move {
+            let mut draining = false;
+            loop {
+                match sample_throughput().await {
+                    Ok(stats) => {
+                        if let Some(eta) = eta_event(&stats, draining) {
+                            let _ = app_handle.emit(QUEUE_ETA_EVENT, &eta);
+                        }
+                        draining = stats.queue_depth > 0;
+                    }
+                    Err(e) => errors::record_last_error(errors::BACKEND, &e),
+                }
+
+                tokio::select! {
+                    _ = tokio::time::sleep(ETA_INTERVAL) => {}
+                    _ = stopped.changed() => break,
+                }
+            }
+        });
+    }
+
+    pub fn stop(&self) {
+        self.stop.lock().unwrap().take();
+    }
+}
+
+// Tauri commands
+#[tauri::command]
+pub async fn get_throughput() -> Result<ThroughputStats, String> {
+    sample_throughput().await
+}
+
+#[tauri::command]
+pub async fn start_queue_eta(monitor: State<'_, Arc<QueueEtaMonitor>>) -> Result<(), String> {
+    monitor.start();
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn stop_queue_eta(monitor: State<'_, Arc<QueueEtaMonitor>>) -> Result<(), String> {
+    monitor.stop();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn counts(completed_items: u64, completed_bytes: u64) -> QueueCounts {
+        QueueCounts {
+            pending_items: 0,
+            processing_items: 0,
+            completed_items,
+            completed_bytes,
+        }
+    }
+
+    #[test]
+    fn no_completions_reports_zeros_without_an_eta() {
+        let mut tracker = ThroughputTracker::new();
+        let stats = tracker.stats(10);
+        assert_eq!(stats.images_per_second, 0.0);
+        assert_eq!(stats.eta_seconds, None);
+
+        let start = Instant::now();
+        tracker.record(start, &counts(5, 500));
+        tracker.record(start + Duration::from_secs(10), &counts(5, 500));
+        let stats = tracker.stats(10);
+        assert_eq!(stats.images_per_second, 0.0);
+        assert_eq!(stats.bytes_per_second, 0.0);
+        assert_eq!(stats.completed_in_window, 0);
+        assert_eq!(stats.eta_seconds, None);
+    }
+
+    #[test]
+    fn rate_and_eta_come_from_completed_counts_over_time() {
+        let mut tracker = ThroughputTracker::new();
+        let start = Instant::now();
+        tracker.record(start, &counts(10, 1_000_000));
+        tracker.record(start + Duration::from_secs(5), &counts(15, 1_500_000));
+        tracker.record(start + Duration::from_secs(10), &counts(20, 2_000_000));
+
+        let stats = tracker.stats(30);
+        assert_eq!(stats.completed_in_window, 10);
+        assert_eq!(stats.images_per_second, 1.0);
+        assert_eq!(stats.bytes_per_second, 100_000.0);
+        assert_eq!(stats.eta_seconds, Some(30.0));
+    }
+
+    #[test]
+    fn old_samples_roll_out_of_the_window() {
+        let mut tracker = ThroughputTracker::new();
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+        // A fast burst long ago, then a slow stretch within the last minute
+        tracker.record(at(0), &counts(0, 0));
+        tracker.record(at(10), &counts(100, 0));
+        tracker.record(at(70), &counts(100, 0));
+        tracker.record(at(130), &counts(112, 0));
+
+        let stats = tracker.stats(0);
+        assert_eq!(stats.completed_in_window, 12);
+        assert_eq!(stats.images_per_second, 0.2);
+    }
+
+    #[test]
+    fn cleared_queue_restarts_the_measurement() {
+        let mut tracker = ThroughputTracker::new();
+        let start = Instant::now();
+        tracker.record(start, &counts(40, 0));
+        tracker.record(start + Duration::from_secs(10), &counts(50, 0));
+        tracker.record(start + Duration::from_secs(20), &counts(2, 0));
+
+        assert_eq!(tracker.stats(5).images_per_second, 0.0);
+        tracker.record(start + Duration::from_secs(30), &counts(7, 0));
+        assert_eq!(tracker.stats(5).images_per_second, 0.5);
+    }
+
+    fn stats(queue_depth: u64, eta_seconds: Option<f64>) -> ThroughputStats {
+        ThroughputStats {
+            images_per_second: eta_seconds.map_or(0.0, |_| 2.0),
+            bytes_per_second: 0.0,
+            window_secs: THROUGHPUT_WINDOW.as_secs(),
+            completed_in_window: 0,
+            queue_depth,
+            eta_seconds,
+        }
+    }
+
+    #[test]
+    fn eta_is_estimating_until_there_is_a_rate() {
+        let eta = eta_event(&stats(40, None), false).unwrap();
+        assert!(eta.estimating);
+        assert_eq!(eta.eta_seconds, None);
+        assert_eq!(eta.queue_depth, 40);
+
+        let eta = eta_event(&stats(40, Some(20.0)), true).unwrap();
+        assert!(!eta.estimating);
+        assert_eq!(eta.eta_seconds, Some(20.0));
+        assert_eq!(eta.images_per_second, 2.0);
+    }
+
+    #[test]
+    fn eta_stops_once_the_queue_drains() {
+        // One final zero as the queue empties, then nothing while it stays empty
+        let last = eta_event(&stats(0, None), true).unwrap();
+        assert_eq!((last.queue_depth, last.eta_seconds), (0, Some(0.0)));
+        assert!(!last.estimating);
+        assert!(eta_event(&stats(0, None), false).is_none());
+    }
+}
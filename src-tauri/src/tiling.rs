@@ -0,0 +1,304 @@
+use crate::commands::get_image_info;
+use crate::pipeline::{ProcessingNode, ProcessingProfile, ProcessingResult};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use tauri::{AppHandle, Emitter};
+
+// Tiles smaller than this spend more time on overlap than on content
+const MIN_TILE_SIZE: u32 = 256;
+// Context each tile takes from each neighbour; seams are blended across both
+// sides of the overlap on reassembly
+const TILE_OVERLAP: u32 = 64;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Tile {
+    pub index: usize,
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TileProgress {
+    pub job_id: String,
+    pub tile: Tile,
+    pub completed: usize,
+    pub total: usize,
+}
+
+#[derive(Serialize)]
+struct TileRequest<'a> {
+    job_id: &'a str,
+    path: &'a str,
+    output_path: &'a str,
+    operations: &'a [ProcessingNode],
+    tile: &'a Tile,
+    overlap: u32,
+}
+
+#[derive(Serialize)]
+struct AssembledTile<'a> {
+    path: &'a str,
+    #[serde(flatten)]
+    tile: &'a Tile,
+}
+
+#[derive(Serialize)]
+struct AssembleRequest<'a> {
+    job_id: &'a str,
+    tiles: Vec<AssembledTile<'a>>,
+    overlap: u32,
+    width: u32,
+    height: u32,
+    output_path: &'a str,
+}
+
+// Start and length of a tile's span along one axis: tile_size of new content
+// plus `overlap` pixels into the neighbour on each side, clipped to the image
+fn tile_span(start: u32, tile_size: u32, overlap: u32, extent: u32) -> (u32, u32) {
+    let from = start.saturating_sub(overlap);
+    let to = start.saturating_add(tile_size).saturating_add(overlap).min(extent);
+    (from, to - from)
+}
+
+fn plan_tiles(width: u32, height: u32, tile_size: u32, overlap: u32) -> Vec<Tile> {
+    let mut tiles = Vec::new();
+    for row in (0..height).step_by(tile_size as usize) {
+        let (y, tile_height) = tile_span(row, tile_size, overlap, height);
+        for column in (0..width).step_by(tile_size as usize) {
+            let (x, tile_width) = tile_span(column, tile_size, overlap, width);
+            tiles.push(Tile {
+                index: tiles.len(),
+                x,
+                y,
+                width: tile_width,
+                height: tile_height,
+            });
+        }
+    }
+    tiles
+}
+
+async fn post_json<T: Serialize>(url: &str, body: &T) -> Result<(), String> {
+    let client = reqwest::Client::new();
+    let response = client
+        .post(url)
+        .json(body)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to connect to processing engine: {}", e))?;
+
+    if !response.status().is_success() {
+        let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+        return Err(format!("Processing engine error: {}", error_text));
+    }
+    Ok(())
+}
+
+struct TiledJob<'a> {
+    job_id: &'a str,
+    input_path: &'a str,
+    output_path: &'a str,
+    nodes: &'a [ProcessingNode],
+    width: u32,
+    height: u32,
+}
+
+async fn process_tiles(
+    app_handle: &AppHandle,
+    job: &TiledJob<'_>,
+    tiles: &[Tile],
+    tile_paths: &[PathBuf],
+) -> Result<(), String> {
+    let TiledJob {
+        job_id,
+        input_path,
+        output_path,
+        nodes,
+        width,
+        height,
+    } = *job;
+
+    for (tile, tile_path) in tiles.iter().zip(tile_paths) {
+        post_json(
+            "http://localhost:8888/process/tile",
+            &TileRequest {
+                job_id,
+                path: input_path,
+                output_path: &tile_path.to_string_lossy(),
+                operations: nodes,
+                tile,
+                overlap: TILE_OVERLAP,
+            },
+        )
+        .await
+        .map_err(|e| format!("Tile {} failed: {}", tile.index, e))?;
+
+        let _ = app_handle.emit(
+            "tile-progress",
+            &TileProgress {
+                job_id: job_id.to_string(),
+                tile: tile.clone(),
+                completed: tile.index + 1,
+                total: tiles.len(),
+            },
+        );
+    }
+
+    let tile_path_strings: Vec<String> = tile_paths
+        .iter()
+        .map(|path| path.to_string_lossy().to_string())
+        .collect();
+    post_json(
+        "http://localhost:8888/process/assemble",
+        &AssembleRequest {
+            job_id,
+            tiles: tiles
+                .iter()
+                .zip(&tile_path_strings)
+                .map(|(tile, path)| AssembledTile { path, tile })
+                .collect(),
+            overlap: TILE_OVERLAP,
+            width,
+            height,
+            output_path,
+        },
+    )
+    .await
+    .map_err(|e| format!("Reassembly failed: {}", e))?;
+
+    // The reassembled image must match the source exactly
+    let assembled = get_image_info(output_path.to_string()).await?;
+    if (assembled.width, assembled.height) != (width, height) {
+        return Err(format!(
+            "Reassembled image is {}x{}, expected {}x{}",
+            assembled.width, assembled.height, width, height
+        ));
+    }
+
+    Ok(())
+}
+
+// Tauri commands
+#[tauri::command]
+pub async fn execute_pipeline_tiled(
+    nodes: Vec<ProcessingNode>,
+    input_path: String,
+    output_path: String,
+    tile_size: u32,
+    app_handle: AppHandle,
+) -> Result<ProcessingResult, String> {
+    let info = get_image_info(input_path.clone()).await?;
+
+    if tile_size < MIN_TILE_SIZE {
+        return Err(format!("Tile size must be at least {} pixels", MIN_TILE_SIZE));
+    }
+    if tile_size >= info.width && tile_size >= info.height {
+        return Err("Tile size covers the whole image; run the pipeline directly".to_string());
+    }
+
+    let started = Instant::now();
+    let job_id = format!("tiled-{}", chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default());
+    let tiles = plan_tiles(info.width, info.height, tile_size, TILE_OVERLAP);
+
+    let extension = Path::new(&input_path)
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_string())
+        .unwrap_or_else(|| "tiff".to_string());
+    let tile_paths: Vec<PathBuf> = tiles
+        .iter()
+        .map(|tile| std::env::temp_dir().join(format!("{}-{}.{}", job_id, tile.index, extension)))
+        .collect();
+
+    let job = TiledJob {
+        job_id: &job_id,
+        input_path: &input_path,
+        output_path: &output_path,
+        nodes: &nodes,
+        width: info.width,
+        height: info.height,
+    };
+    let outcome = process_tiles(&app_handle, &job, &tiles, &tile_paths).await;
+
+    // Tiles are intermediate; never leave them behind
+    for tile_path in &tile_paths {
+        let _ = tokio::fs::remove_file(tile_path).await;
+    }
+
+    Ok(ProcessingResult {
+        session_id: None,
+        success: outcome.is_ok(),
+        input_path,
+        input_paths: Vec::new(),
+        output_path,
+        operations_applied: nodes.len() as u32,
+        processing_time_ms: started.elapsed().as_millis() as u64,
+        profile: ProcessingProfile::default(),
+        folder_id: None,
+        encoding: None,
+        models: Vec::new(),
+        output_sharpening: None,
+        warning: None,
+        error: outcome.err(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spans(tiles: &[Tile]) -> Vec<(u32, u32, u32, u32)> {
+        tiles
+            .iter()
+            .map(|tile| (tile.x, tile.y, tile.width, tile.height))
+            .collect()
+    }
+
+    #[test]
+    fn interior_tiles_overlap_on_every_side() {
+        let tiles = plan_tiles(768, 512, 256, 64);
+        assert_eq!(
+            spans(&tiles),
+            [
+                (0, 0, 320, 320),
+                (192, 0, 384, 320),
+                (448, 0, 320, 320),
+                (0, 192, 320, 320),
+                (192, 192, 384, 320),
+                (448, 192, 320, 320),
+            ]
+        );
+        assert!(tiles.iter().enumerate().all(|(i, tile)| tile.index == i));
+    }
+
+    #[test]
+    fn ragged_last_row_and_column_are_clipped_to_the_image() {
+        let tiles = plan_tiles(600, 300, 256, 64);
+        assert_eq!(
+            spans(&tiles),
+            [
+                (0, 0, 320, 300),
+                (192, 0, 384, 300),
+                (448, 0, 152, 300),
+                (0, 192, 320, 108),
+                (192, 192, 384, 108),
+                (448, 192, 152, 108),
+            ]
+        );
+        // Every edge tile still reaches the image border exactly
+        assert!(tiles.iter().all(|tile| tile.x + tile.width <= 600 && tile.y + tile.height <= 300));
+        assert_eq!(tiles.iter().map(|tile| tile.x + tile.width).max(), Some(600));
+        assert_eq!(tiles.iter().map(|tile| tile.y + tile.height).max(), Some(300));
+    }
+
+    #[test]
+    fn image_smaller_than_a_tile_is_a_single_tile() {
+        assert_eq!(spans(&plan_tiles(100, 80, 256, 64)), [(0, 0, 100, 80)]);
+        assert_eq!(
+            spans(&plan_tiles(100, 400, 256, 64)),
+            [(0, 0, 100, 320), (0, 192, 100, 208)]
+        );
+    }
+}